@@ -1,6 +1,82 @@
-use crate::{terminal_to_string, typed_syntax_node_to_string_without_trivia};
+use crate::{Span, terminal_to_string, typed_syntax_node_to_string_without_trivia};
+use cairo_lang_syntax::node::kind::SyntaxKind;
 use cairo_lang_syntax::node::{SyntaxNode, TypedSyntaxNode};
 use salsa::Database;
+use thiserror::Error;
+
+#[derive(Clone, Debug, Error, PartialEq)]
+pub enum AstError {
+    #[error("unexpected {found:?} node, expected {expected}")]
+    UnexpectedKind {
+        expected: &'static str,
+        found: SyntaxKind,
+        span: Span,
+    },
+    #[error("missing {node}")]
+    Missing { node: &'static str, span: Span },
+}
+
+pub type AstResult<T> = Result<T, AstError>;
+
+impl AstError {
+    pub fn unexpected_kind(
+        db: &dyn Database,
+        node: SyntaxNode<'_>,
+        expected: &'static str,
+    ) -> Self {
+        AstError::UnexpectedKind {
+            expected,
+            found: node.kind(db),
+            span: node.span_without_trivia(db).into(),
+        }
+    }
+
+    pub fn missing(db: &dyn Database, node: SyntaxNode<'_>, name: &'static str) -> Self {
+        AstError::Missing {
+            node: name,
+            span: node.span_without_trivia(db).into(),
+        }
+    }
+
+    pub fn span(&self) -> Span {
+        match self {
+            AstError::UnexpectedKind { span, .. } | AstError::Missing { span, .. } => *span,
+        }
+    }
+}
+
+/// The name reported for a `Missing` node of `kind`, `None` for other kinds.
+pub(crate) fn missing_node_name(kind: SyntaxKind) -> Option<&'static str> {
+    match kind {
+        SyntaxKind::ExprMissing => Some("expression"),
+        SyntaxKind::PathSegmentMissing => Some("path segment"),
+        SyntaxKind::StatementMissing => Some("statement"),
+        SyntaxKind::ModuleItemMissing => Some("item"),
+        SyntaxKind::TraitItemMissing => Some("trait item"),
+        SyntaxKind::ImplItemMissing => Some("impl item"),
+        SyntaxKind::MacroRepetitionOperatorMissing => Some("repetition operator"),
+        SyntaxKind::MacroParamKindMissing => Some("macro parameter kind"),
+        _ => None,
+    }
+}
+
+/// Fails on the first `Missing` node of `node`'s subtree, in source order.
+pub fn check_missing<'db>(node: SyntaxNode<'db>, db: &'db dyn Database) -> AstResult<()> {
+    match node
+        .descendants(db)
+        .find_map(|node| missing_node_name(node.kind(db)).map(|name| (node, name)))
+    {
+        Some((node, name)) => Err(AstError::missing(db, node, name)),
+        None => Ok(()),
+    }
+}
+
+/// Strip the module path and generic arguments from a type name.
+pub fn short_type_name<T>() -> &'static str {
+    let name = std::any::type_name::<T>();
+    let name = name.split('<').next().unwrap_or(name);
+    name.rsplit("::").next().unwrap_or(name)
+}
 
 pub trait FromAst<'db, T>
 where
@@ -30,6 +106,107 @@ where
     }
 }
 
+/// Fallible counterpart of [`FromAst`], reporting unexpected and missing nodes instead of
+/// panicking or producing `Missing` variants.
+///
+/// Generated implementations are [`checked_from_ast`]: the subtree is checked once for
+/// `Missing` nodes and then converted with [`FromAst`], so both traits build the same node.
+pub trait TryFromAst<'db, T>
+where
+    T: TypedSyntaxNode<'db>,
+    Self: Sized,
+{
+    fn try_from_ast(ast: T, db: &'db dyn Database) -> AstResult<Self>;
+    fn try_from_syntax_node(db: &'db dyn Database, node: SyntaxNode<'db>) -> AstResult<Self> {
+        match T::cast(db, node) {
+            Some(ast) => Self::try_from_ast(ast, db),
+            None => Err(AstError::unexpected_kind(db, node, short_type_name::<T>())),
+        }
+    }
+}
+
+pub trait AstTryInto<'db, T>
+where
+    Self: TypedSyntaxNode<'db>,
+{
+    fn ast_try_into(self, db: &'db dyn Database) -> AstResult<T>;
+}
+
+impl<'db, T, U> AstTryInto<'db, U> for T
+where
+    T: TypedSyntaxNode<'db>,
+    U: TryFromAst<'db, T>,
+{
+    fn ast_try_into(self, db: &'db dyn Database) -> AstResult<U> {
+        U::try_from_ast(self, db)
+    }
+}
+
+/// [`FromAst::from_ast`] if `ast` contains no `Missing` node, the shared body of the generated
+/// [`TryFromAst`] implementations.
+pub fn checked_from_ast<'db, T, U>(ast: T, db: &'db dyn Database) -> AstResult<U>
+where
+    T: TypedSyntaxNode<'db>,
+    U: FromAst<'db, T>,
+{
+    check_missing(ast.as_syntax_node(), db)?;
+    Ok(U::from_ast(ast, db))
+}
+
+/// Collects the `///` doc comments leading `node`, including those placed between its
+/// attributes and the rest of the node.
+pub fn doc_comments<'db>(node: SyntaxNode<'db>, db: &'db dyn Database) -> Vec<String> {
+    leading_comments(node, db, SyntaxKind::TokenSingleLineDocComment, "///")
+}
+
+/// Collects the `//!` header doc comments leading `node`.
+pub fn inner_doc_comments<'db>(node: SyntaxNode<'db>, db: &'db dyn Database) -> Vec<String> {
+    leading_comments(node, db, SyntaxKind::TokenSingleLineInnerComment, "//!")
+}
+
+/// The tokens whose leading trivia belongs to `node` rather than to one of its parts: the first
+/// token of each attribute and the first token following the attributes.
+fn leading_tokens<'db>(node: SyntaxNode<'db>, db: &'db dyn Database) -> Vec<SyntaxNode<'db>> {
+    let mut tokens = vec![];
+    for child in node.get_children(db).iter() {
+        if child.kind(db) == SyntaxKind::AttributeList {
+            tokens.extend(
+                child
+                    .get_children(db)
+                    .iter()
+                    .filter_map(|attribute| attribute.tokens(db).next()),
+            );
+        } else if let Some(token) = child.tokens(db).next() {
+            tokens.push(token);
+            break;
+        }
+    }
+    tokens
+}
+
+fn leading_comments<'db>(
+    node: SyntaxNode<'db>,
+    db: &'db dyn Database,
+    kind: SyntaxKind,
+    prefix: &str,
+) -> Vec<String> {
+    leading_tokens(node, db)
+        .into_iter()
+        .flat_map(|terminal| {
+            terminal.get_children(db)[0]
+                .get_children(db)
+                .iter()
+                .copied()
+        })
+        .filter(|trivium| trivium.kind(db) == kind)
+        .map(|trivium| {
+            let text = trivium.get_text(db).trim_end();
+            let text = text.strip_prefix(prefix).unwrap_or(text);
+            text.strip_prefix(' ').unwrap_or(text).to_string()
+        })
+        .collect()
+}
+
 pub trait AstToString<'db> {
     fn to_string(&self, db: &'db dyn Database) -> String;
 }
@@ -53,6 +230,25 @@ where
     }
 }
 
+impl<'db, T> TryFromAst<'db, T> for String
+where
+    T: AstToString<'db> + TypedSyntaxNode<'db>,
+{
+    fn try_from_ast(ast: T, db: &'db dyn Database) -> AstResult<Self> {
+        checked_from_ast(ast, db)
+    }
+}
+
+impl<'db, T, S> TryFromAst<'db, T> for Box<S>
+where
+    S: TryFromAst<'db, T>,
+    T: TypedSyntaxNode<'db>,
+{
+    fn try_from_ast(ast: T, db: &'db dyn Database) -> AstResult<Self> {
+        S::try_from_ast(ast, db).map(Box::new)
+    }
+}
+
 typed_syntax_node_to_string_without_trivia! { Expr }
 typed_syntax_node_to_string_without_trivia! { TypeClause.ty }
 
@@ -1,19 +1,100 @@
+use crate::ast::checked_from_ast;
+use crate::span::node_span;
 use crate::{
-    Expr, ExprPath, Modifier, from_typed_syntax_node, syntax_enum, syntax_option, syntax_type,
-    terminal_to_string, typed_syntax_node_to_string_without_trivia, vec_from_element_list,
+    AstInto, AstResult, AttributeError, CairoFormat, Expr, ExprPath, FromAst, FromAttribute,
+    Modifier, Span, Spanned, TryFromAst, from_typed_syntax_node, syntax_display, syntax_enum,
+    syntax_option, syntax_type, terminal_to_string, typed_syntax_node_to_string_without_trivia,
+    vec_from_element_list,
 };
+use cairo_lang_syntax::node::ast;
 use delegate::delegate;
+use salsa::Database;
+use std::hash::{Hash, Hasher};
 use std::mem;
 use std::ops::Deref;
 
-syntax_type! {
-    Attribute{
-        path[attr]: ExprPath,
-        arguments: Option<Vec<Arg>>,
-        path_str[attr]: String,
+/// An attribute such as `#[derive(Drop)]`.
+///
+/// The path is only reachable through [`Attribute::path`] and [`Attribute::set_path`] so that
+/// the cached [`Attribute::path_str`] always matches it.
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(from = "AttributeFields")
+)]
+pub struct Attribute {
+    path: ExprPath,
+    pub arguments: Option<Vec<Arg>>,
+    path_str: String,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub span: Option<Span>,
+}
+
+/// The serialized fields of an [`Attribute`], `path_str` is recomputed rather than trusted.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct AttributeFields {
+    path: ExprPath,
+    arguments: Option<Vec<Arg>>,
+    #[serde(default)]
+    span: Option<Span>,
+}
+
+#[cfg(feature = "serde")]
+impl From<AttributeFields> for Attribute {
+    fn from(fields: AttributeFields) -> Self {
+        Attribute {
+            arguments: fields.arguments,
+            span: fields.span,
+            ..Attribute::new(fields.path)
+        }
+    }
+}
+
+impl PartialEq for Attribute {
+    fn eq(&self, other: &Self) -> bool {
+        self.path == other.path && self.arguments == other.arguments
+    }
+}
+
+impl Eq for Attribute {}
+
+impl Hash for Attribute {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.path.hash(state);
+        self.arguments.hash(state);
+    }
+}
+
+impl Spanned for Attribute {
+    fn span(&self) -> Option<Span> {
+        self.span
+    }
+}
+
+impl<'db> FromAst<'db, ast::Attribute<'db>> for Attribute {
+    fn from_ast(ast: ast::Attribute<'db>, db: &'db dyn Database) -> Self {
+        let path: ExprPath = ast.attr(db).ast_into(db);
+        Attribute {
+            arguments: ast.arguments(db).ast_into(db),
+            span: Some(node_span(&ast, db)),
+            ..Attribute::new(path)
+        }
     }
 }
 
+impl<'db> TryFromAst<'db, ast::Attribute<'db>> for Attribute {
+    fn try_from_ast(ast: ast::Attribute<'db>, db: &'db dyn Database) -> AstResult<Self> {
+        checked_from_ast(ast, db)
+    }
+}
+
+syntax_display!(Attribute);
+
 syntax_type! {
     Arg {
         modifiers: Vec<Modifier>,
@@ -50,6 +131,48 @@ syntax_option! {OptionArgListParenthesized{ArgListParenthesized: Vec<Arg>}}
 const DERIVE_PATH: &str = "derive";
 
 impl Attribute {
+    /// `#[path]` without arguments.
+    pub fn new(path: impl Into<ExprPath>) -> Self {
+        let path = path.into();
+        Attribute {
+            path_str: path.stringify(),
+            path,
+            arguments: None,
+            span: None,
+        }
+    }
+
+    /// `#[derive(...)]` with each trait as an argument.
+    pub fn derive(traits: impl IntoIterator<Item = impl Into<ExprPath>>) -> Self {
+        Attribute::new(DERIVE_PATH).with_args(traits.into_iter().map(derive_arg))
+    }
+
+    /// Appends `args`, turning `#[path]` into `#[path(...)]`.
+    pub fn with_args<A: Into<Arg>>(mut self, args: impl IntoIterator<Item = A>) -> Self {
+        self.arguments
+            .get_or_insert_default()
+            .extend(args.into_iter().map(Into::into));
+        self
+    }
+
+    pub fn path(&self) -> &ExprPath {
+        &self.path
+    }
+
+    pub fn set_path(&mut self, path: impl Into<ExprPath>) {
+        self.path = path.into();
+        self.path_str = self.path.stringify();
+    }
+
+    /// The path for in-place visitors, which must call [`Attribute::sync_path_str`] after.
+    pub(crate) fn path_mut(&mut self) -> &mut ExprPath {
+        &mut self.path
+    }
+
+    pub(crate) fn sync_path_str(&mut self) {
+        self.path_str = self.path.stringify();
+    }
+
     /// Get cached string representation of path (zero allocation)
     pub fn path_str(&self) -> &str {
         &self.path_str
@@ -59,15 +182,19 @@ impl Attribute {
         self.path_str.clone()
     }
 
+    pub fn is_derive(&self) -> bool {
+        self.path_str == DERIVE_PATH
+    }
+
     pub fn get_derives(&self) -> Vec<String> {
-        if self.path_str == DERIVE_PATH {
-            if let Some(args) = &self.arguments {
-                return args
-                    .iter()
-                    .filter_map(Arg::as_unnamed)
-                    .map(|expr| expr.to_string())
-                    .collect();
-            }
+        if self.is_derive()
+            && let Some(args) = &self.arguments
+        {
+            return args
+                .iter()
+                .filter_map(Arg::as_unnamed)
+                .map(|expr| expr.to_string())
+                .collect();
         }
         vec![]
     }
@@ -84,6 +211,41 @@ impl Attribute {
             _ => None,
         }
     }
+
+    /// Sets the named argument `name: value`, replacing an existing one of the same name.
+    pub fn set_arg(&mut self, name: &str, value: impl Into<Expr>) {
+        let value = value.into();
+        let args = self.arguments.get_or_insert_default();
+        match args.iter_mut().find_map(|arg| match &mut arg.clause {
+            ArgClause::Named(named) if named.name == name => Some(named),
+            _ => None,
+        }) {
+            Some(named) => named.value = value,
+            None => args.push(NamedArg::new(name, value).into()),
+        }
+    }
+
+    /// Removes `name` from the arguments of a derive, returning whether it was there.
+    fn remove_derive_arg(&mut self, name: &str) -> bool {
+        if !self.is_derive() {
+            return false;
+        }
+        let Some(args) = self.arguments.as_mut() else {
+            return false;
+        };
+        let len = args.len();
+        args.retain(|arg| !is_derive_of(arg, name));
+        args.len() != len
+    }
+}
+
+fn derive_arg(path: impl Into<ExprPath>) -> Arg {
+    Expr::Path(path.into()).into()
+}
+
+fn is_derive_of(arg: &Arg, name: &str) -> bool {
+    arg.as_unnamed()
+        .is_some_and(|expr| expr.to_string() == name)
 }
 
 pub trait AttributesTrait {
@@ -102,6 +264,38 @@ pub trait AttributesTrait {
             .iter()
             .find(|attr| attr.path_str() == name)
     }
+    /// Reads the arguments of the first attribute named `name`, `None` if there is none.
+    fn get_attribute_args<A: FromAttribute>(
+        &self,
+        name: &str,
+    ) -> Option<Result<A, AttributeError>> {
+        self.get_attribute(name).map(A::from_attribute)
+    }
+    /// Removes every attribute named `name`, returning them.
+    fn remove_attribute(&mut self, name: &str) -> Vec<Attribute> {
+        let (removed, kept) = self
+            .take_attributes()
+            .into_iter()
+            .partition(|attr| attr.path_str() == name);
+        self.update_attributes(kept);
+        removed
+    }
+    fn retain_attributes(&mut self, f: impl FnMut(&Attribute) -> bool) {
+        self.attributes_mut().retain(f);
+    }
+    /// Sets `arg: value` on the first attribute named `name`, adding `#[name(arg: value)]` if
+    /// there is none.
+    fn set_attribute_arg(&mut self, name: &str, arg: &str, value: impl Into<Expr>) {
+        let attributes = self.attributes_mut();
+        let index = match attributes.iter().position(|attr| attr.path_str() == name) {
+            Some(index) => index,
+            None => {
+                attributes.push(Attribute::new(name));
+                attributes.len() - 1
+            }
+        };
+        attributes[index].set_arg(arg, value);
+    }
     fn update_attributes(&mut self, attributes: Vec<Attribute>) {
         *self.attributes_mut() = attributes;
     }
@@ -117,6 +311,75 @@ pub trait AttributesTrait {
             .flat_map(|attr| attr.get_derives())
             .collect()
     }
+    fn has_derive(&self, name: &str) -> bool {
+        self.attributes()
+            .iter()
+            .filter(|attr| attr.is_derive())
+            .flat_map(|attr| attr.arguments.iter().flatten())
+            .any(|arg| is_derive_of(arg, name))
+    }
+    /// Adds `path` to the first `#[derive]`, adding one if there is none, unless it is
+    /// already derived.
+    fn add_derive(&mut self, path: impl Into<ExprPath>) {
+        let path = path.into();
+        if self.has_derive(&path.stringify()) {
+            return;
+        }
+        let attributes = self.attributes_mut();
+        match attributes.iter_mut().find(|attr| attr.is_derive()) {
+            Some(derive) => derive
+                .arguments
+                .get_or_insert_default()
+                .push(derive_arg(path)),
+            None => attributes.push(Attribute::derive([path])),
+        }
+    }
+    /// Removes `name` from every `#[derive]`, dropping those left empty, returning whether it
+    /// was derived.
+    fn remove_derive(&mut self, name: &str) -> bool {
+        let attributes = self.attributes_mut();
+        let mut removed = false;
+        attributes.retain_mut(|attr| {
+            if !attr.remove_derive_arg(name) {
+                return true;
+            }
+            removed = true;
+            attr.arguments.as_ref().is_some_and(|args| !args.is_empty())
+        });
+        removed
+    }
+}
+
+impl NamedArg {
+    pub fn new(name: impl Into<String>, value: impl Into<Expr>) -> Self {
+        NamedArg {
+            name: name.into(),
+            value: value.into(),
+            span: None,
+        }
+    }
+}
+
+impl From<ArgClause> for Arg {
+    fn from(clause: ArgClause) -> Self {
+        Arg {
+            modifiers: vec![],
+            clause,
+            span: None,
+        }
+    }
+}
+
+impl From<Expr> for Arg {
+    fn from(value: Expr) -> Self {
+        ArgClause::Unnamed(value).into()
+    }
+}
+
+impl From<NamedArg> for Arg {
+    fn from(arg: NamedArg) -> Self {
+        ArgClause::Named(arg).into()
+    }
 }
 
 impl Deref for Arg {
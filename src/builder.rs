@@ -0,0 +1,309 @@
+use crate::item::{FunctionDeclaration, FunctionSignature, FunctionWithBody, Impl, ImplItem};
+use crate::statement::ExprStatement;
+use crate::{
+    Attribute, AttributesTrait, Enum, Expr, ExprPath, GenericParam, Member, Modifier, Param,
+    Statement, Struct, Type, Variant, Visibility,
+};
+
+impl Member {
+    pub fn new(name: impl Into<String>, ty: impl Into<Type>) -> Self {
+        Member {
+            docs: vec![],
+            attributes: vec![],
+            visibility: Visibility::Default,
+            name: name.into(),
+            ty: ty.into(),
+            span: None,
+        }
+    }
+}
+
+impl Variant {
+    pub fn new(name: impl Into<String>, ty: Option<Type>) -> Self {
+        Variant {
+            docs: vec![],
+            attributes: vec![],
+            name: name.into(),
+            type_clause: ty,
+            span: None,
+        }
+    }
+}
+
+impl Param {
+    pub fn new(name: impl Into<String>, ty: impl Into<Type>) -> Self {
+        Param {
+            modifiers: vec![],
+            name: name.into(),
+            type_clause: Some(ty.into()),
+            span: None,
+        }
+    }
+}
+
+/// Methods shared by every builder, `$generics` is the field path of the node's generic
+/// parameters.
+macro_rules! builder_common {
+    ($builder:ident, $($generics:ident).+) => {
+        impl $builder {
+            /// Appends a line of documentation, without the `///`.
+            pub fn doc(mut self, line: impl Into<String>) -> Self {
+                self.node.docs.push(line.into());
+                self
+            }
+
+            pub fn attribute(mut self, attribute: Attribute) -> Self {
+                self.node.attributes.push(attribute);
+                self
+            }
+
+            /// Adds traits to the `#[derive(...)]` attribute, creating it if needed.
+            pub fn derive(mut self, traits: impl IntoIterator<Item = impl Into<ExprPath>>) -> Self {
+                for path in traits {
+                    self.node.add_derive(path);
+                }
+                self
+            }
+
+            pub fn visibility(mut self, visibility: Visibility) -> Self {
+                self.node.visibility = visibility;
+                self
+            }
+
+            pub fn public(self) -> Self {
+                self.visibility(Visibility::Pub(None))
+            }
+
+            pub fn generic_param(mut self, param: GenericParam) -> Self {
+                self.node.$($generics).+.get_or_insert_default().push(param);
+                self
+            }
+
+            pub fn type_param(self, name: impl Into<String>) -> Self {
+                self.generic_param(GenericParam::Type(name.into()))
+            }
+        }
+    };
+}
+
+/// Builds a [`Struct`], see [`Struct::builder`].
+#[derive(Clone, Debug)]
+pub struct StructBuilder {
+    node: Struct,
+}
+
+builder_common!(StructBuilder, generic_params);
+
+impl Struct {
+    /// A private struct without attributes, generic parameters or members.
+    pub fn builder(name: impl Into<String>) -> StructBuilder {
+        StructBuilder {
+            node: Struct {
+                docs: vec![],
+                attributes: vec![],
+                visibility: Visibility::Default,
+                name: name.into(),
+                generic_params: None,
+                members: vec![],
+                span: None,
+            },
+        }
+    }
+}
+
+impl StructBuilder {
+    pub fn build(self) -> Struct {
+        self.node
+    }
+
+    pub fn member(self, name: impl Into<String>, ty: impl Into<Type>) -> Self {
+        self.member_with(Member::new(name, ty))
+    }
+
+    pub fn member_with(mut self, member: Member) -> Self {
+        self.node.members.push(member);
+        self
+    }
+}
+
+/// Builds an [`Enum`], see [`Enum::builder`].
+#[derive(Clone, Debug)]
+pub struct EnumBuilder {
+    node: Enum,
+}
+
+builder_common!(EnumBuilder, generic_params);
+
+impl Enum {
+    /// A private enum without attributes, generic parameters or variants.
+    pub fn builder(name: impl Into<String>) -> EnumBuilder {
+        EnumBuilder {
+            node: Enum {
+                docs: vec![],
+                attributes: vec![],
+                visibility: Visibility::Default,
+                name: name.into(),
+                generic_params: None,
+                variants: vec![],
+                span: None,
+            },
+        }
+    }
+}
+
+impl EnumBuilder {
+    pub fn build(self) -> Enum {
+        self.node
+    }
+
+    pub fn variant(self, name: impl Into<String>, ty: impl Into<Type>) -> Self {
+        self.variant_with(Variant::new(name, Some(ty.into())))
+    }
+
+    pub fn unit_variant(self, name: impl Into<String>) -> Self {
+        self.variant_with(Variant::new(name, None))
+    }
+
+    pub fn variant_with(mut self, variant: Variant) -> Self {
+        self.node.variants.push(variant);
+        self
+    }
+}
+
+/// Builds a [`FunctionWithBody`], see [`FunctionWithBody::builder`].
+#[derive(Clone, Debug)]
+pub struct FunctionBuilder {
+    node: FunctionWithBody,
+    tail: Option<Expr>,
+}
+
+builder_common!(FunctionBuilder, declaration.generic_params);
+
+impl FunctionWithBody {
+    /// A private function without parameters, return type or body.
+    pub fn builder(name: impl Into<String>) -> FunctionBuilder {
+        FunctionBuilder {
+            node: FunctionWithBody {
+                docs: vec![],
+                attributes: vec![],
+                visibility: Visibility::Default,
+                declaration: FunctionDeclaration {
+                    is_const: false,
+                    name: name.into(),
+                    generic_params: None,
+                    signature: FunctionSignature {
+                        parameters: vec![],
+                        return_type: None,
+                        implicits_clause: None,
+                        no_panic: false,
+                        span: None,
+                    },
+                    span: None,
+                },
+                body: vec![],
+                span: None,
+            },
+            tail: None,
+        }
+    }
+}
+
+impl FunctionBuilder {
+    pub fn build(self) -> FunctionWithBody {
+        let mut node = self.node;
+        node.body.extend(self.tail.map(|expr| {
+            Statement::Expr(ExprStatement {
+                attributes: vec![],
+                expr,
+                semicolon: false,
+                span: None,
+            })
+        }));
+        node
+    }
+
+    pub fn param(self, name: impl Into<String>, ty: impl Into<Type>) -> Self {
+        self.param_with(Param::new(name, ty))
+    }
+
+    /// A `ref` parameter, as in `ref self: ContractState`.
+    pub fn ref_param(self, name: impl Into<String>, ty: impl Into<Type>) -> Self {
+        self.param_with(Param {
+            modifiers: vec![Modifier::Ref],
+            ..Param::new(name, ty)
+        })
+    }
+
+    pub fn param_with(mut self, param: Param) -> Self {
+        self.node.declaration.signature.parameters.push(param);
+        self
+    }
+
+    pub fn returns(mut self, ty: impl Into<Type>) -> Self {
+        self.node.declaration.signature.return_type = Some(ty.into());
+        self
+    }
+
+    pub fn nopanic(mut self) -> Self {
+        self.node.declaration.signature.no_panic = true;
+        self
+    }
+
+    pub fn statement(mut self, statement: Statement) -> Self {
+        self.node.body.push(statement);
+        self
+    }
+
+    pub fn statements(mut self, statements: impl IntoIterator<Item = Statement>) -> Self {
+        self.node.body.extend(statements);
+        self
+    }
+
+    /// Ends the body with `expr` as its value, after any statement added before or after this
+    /// call. A later call replaces the tail.
+    pub fn tail(mut self, expr: Expr) -> Self {
+        self.tail = Some(expr);
+        self
+    }
+}
+
+/// Builds an [`Impl`], see [`Impl::builder`].
+#[derive(Clone, Debug)]
+pub struct ImplBuilder {
+    node: Impl,
+}
+
+builder_common!(ImplBuilder, generic_params);
+
+impl Impl {
+    /// A private impl of `trait_path` with an empty body.
+    pub fn builder(name: impl Into<String>, trait_path: impl Into<ExprPath>) -> ImplBuilder {
+        ImplBuilder {
+            node: Impl {
+                docs: vec![],
+                attributes: vec![],
+                visibility: Visibility::Default,
+                name: name.into(),
+                generic_params: None,
+                trait_path: trait_path.into(),
+                body: Some(vec![]),
+                span: None,
+            },
+        }
+    }
+}
+
+impl ImplBuilder {
+    pub fn build(self) -> Impl {
+        self.node
+    }
+
+    pub fn function(self, function: FunctionWithBody) -> Self {
+        self.item(ImplItem::Function(function))
+    }
+
+    pub fn item(mut self, item: ImplItem) -> Self {
+        self.node.body.get_or_insert_default().push(item);
+        self
+    }
+}
@@ -2,9 +2,11 @@ use cairo_lang_syntax::node::TypedSyntaxNode;
 use cairo_lang_syntax::node::ast::TokenTreeNode;
 use salsa::Database;
 
+use crate::ast::checked_from_ast;
 use crate::{
-    Expr, FromAst, from_typed_syntax_node, syntax_enum, syntax_option, syntax_terminal_bool,
-    syntax_terminal_enum, syntax_type, terminal_to_string, vec_from_element_list,
+    AstResult, FromAst, TryFromAst, Type, from_typed_syntax_node, syntax_enum, syntax_option,
+    syntax_terminal_bool, syntax_terminal_enum, syntax_type, terminal_to_string,
+    vec_from_element_list,
 };
 
 pub trait NameTrait {
@@ -12,6 +14,14 @@ pub trait NameTrait {
     fn set_name(&mut self, new_name: String);
 }
 
+pub trait DocsTrait {
+    fn docs(&self) -> &[String];
+    fn docs_mut(&mut self) -> &mut Vec<String>;
+    fn set_docs(&mut self, docs: Vec<String>) {
+        *self.docs_mut() = docs;
+    }
+}
+
 pub trait VisibilityTrait {
     fn visibility(&self) -> &Visibility;
     fn set_visibility(&mut self, new_visibility: Visibility);
@@ -21,7 +31,7 @@ syntax_type! {
     Param{
         modifiers: Vec<Modifier>,
         name: String,
-        type_clause: Option<Expr>,
+        type_clause: Option<Type>,
     }
 }
 
@@ -60,6 +70,14 @@ syntax_option! {OptionVisibilityPubArgumentClause{VisibilityPubArgumentClause: S
 
 impl<'db> FromAst<'db, TokenTreeNode<'db>> for String {
     fn from_ast(node: TokenTreeNode<'db>, db: &'db dyn Database) -> Self {
-        node.as_syntax_node().get_text(db).to_string()
+        node.as_syntax_node()
+            .get_text_without_trivia(db)
+            .to_string(db)
+    }
+}
+
+impl<'db> TryFromAst<'db, TokenTreeNode<'db>> for String {
+    fn try_from_ast(node: TokenTreeNode<'db>, db: &'db dyn Database) -> AstResult<Self> {
+        checked_from_ast(node, db)
     }
 }
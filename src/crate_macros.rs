@@ -14,7 +14,7 @@ macro_rules! terminal_to_string {
 
     {$($terminal:ident $(. $($methods:ident).+)?),* $(,)?} => {
         $(
-            impl<'db> crate::AstToString<'db> for cairo_lang_syntax::node::ast::$terminal<'db> {
+            impl<'db> $crate::AstToString<'db> for cairo_lang_syntax::node::ast::$terminal<'db> {
 
                 fn to_string(&self, db: &'db dyn salsa::Database) -> String {
                     use cairo_lang_syntax::node::Terminal;
@@ -28,7 +28,7 @@ macro_rules! terminal_to_string {
 #[macro_export]
 macro_rules! typed_syntax_node_to_string_without_trivia {
     {$typed_syntax_node:ident $(. $($methods:ident).+)?} => {
-        impl<'db> crate::AstToString<'db> for cairo_lang_syntax::node::ast::$typed_syntax_node<'db> {
+        impl<'db> $crate::AstToString<'db> for cairo_lang_syntax::node::ast::$typed_syntax_node<'db> {
             fn to_string(&self, db: &'db dyn salsa::Database) -> String {
                 use cairo_lang_syntax::node::TypedSyntaxNode;
                 self$(.$($methods(db)).+)?.as_syntax_node().get_text_without_trivia(db).to_string(db)
@@ -40,33 +40,41 @@ macro_rules! typed_syntax_node_to_string_without_trivia {
 #[macro_export]
 macro_rules! from_typed_syntax_node {
     {$typed_syntax_node:ident $(. $($methods:ident).+)?, $($syntax_type:tt)+} => {
-        impl<'db> crate::FromAst<'db, cairo_lang_syntax::node::ast::$typed_syntax_node<'db>> for $($syntax_type)+ {
+        impl<'db> $crate::FromAst<'db, cairo_lang_syntax::node::ast::$typed_syntax_node<'db>> for $($syntax_type)+ {
             fn from_ast(ast: cairo_lang_syntax::node::ast::$typed_syntax_node<'db>, db: &'db dyn salsa::Database) -> Self {
-                use crate::AstInto;
+                use $crate::AstInto;
                 ast$(.$($methods(db)).+)?.ast_into(db)
             }
         }
+
+        impl<'db> $crate::TryFromAst<'db, cairo_lang_syntax::node::ast::$typed_syntax_node<'db>> for $($syntax_type)+ {
+            fn try_from_ast(ast: cairo_lang_syntax::node::ast::$typed_syntax_node<'db>, db: &'db dyn salsa::Database) -> $crate::AstResult<Self> {
+                $crate::ast::checked_from_ast(ast, db)
+            }
+        }
     };
 }
 
 #[macro_export]
 macro_rules! vec_from_element_list {
     {$list:ident $(. $($methods:ident).+)?, $element:ident} => {
-        impl<'db> crate::FromAst<'db, cairo_lang_syntax::node::ast::$list<'db>> for Vec<$element> {
+        impl<'db> $crate::FromAst<'db, cairo_lang_syntax::node::ast::$list<'db>> for Vec<$element> {
             fn from_ast(ast: cairo_lang_syntax::node::ast::$list<'db>, db: &'db dyn salsa::Database) -> Vec<$element> {
-                use crate::AstInto;
+                use $crate::AstInto;
                 ast$(.$($methods(db)).+)?.elements(db).into_iter().map(|e| e.ast_into(db)).collect()
             }
         }
+
+        $crate::vec_try_from_element_list!{$list $(. $($methods).+)?, $element}
     };
 }
 
 #[macro_export]
 macro_rules! vec_try_from_element_list {
     {$list:ident $(. $($methods:ident).+)?, $element:ident} => {
-        impl<'db> TryFromAst<'db, cairo_lang_syntax::node::ast::$list<'db>> for Vec<$element> {
-            fn try_from_ast(ast: cairo_lang_syntax::node::ast::$list<'db>, db: &'db dyn Database) -> IntrospectResult<Vec<$element>> {
-                ast$(.$($methods(db)).+)?.elements(db).into_iter().map(|e| e.ast_try_into(db)).collect()
+        impl<'db> $crate::TryFromAst<'db, cairo_lang_syntax::node::ast::$list<'db>> for Vec<$element> {
+            fn try_from_ast(ast: cairo_lang_syntax::node::ast::$list<'db>, db: &'db dyn salsa::Database) -> $crate::AstResult<Vec<$element>> {
+                $crate::ast::checked_from_ast(ast, db)
             }
         }
     };
@@ -108,57 +116,119 @@ macro_rules! syntax_type {
     {
         $struct_name:ident[$ast_type:ident] { $($field:ident $([ $method:ident ])?: $field_type:ty),* $(,)? }
     } => {
-        #[derive(Clone, Debug, PartialEq)]
+        $crate::syntax_type!(@node $struct_name { $($field: $field_type),* });
+        $crate::syntax_type!(@from_ast $struct_name, $ast_type { $($field $([ $method ])?),* });
+        $crate::syntax_display!($struct_name);
+    };
+
+    // Without AST type override - use struct name
+    {
+        $struct_name:ident { $($field:ident $([ $method:ident ])?: $field_type:ty),* $(,)? }
+    } => {
+        $crate::syntax_type!(@node $struct_name { $($field: $field_type),* });
+        $crate::syntax_type!(@from_ast $struct_name, $struct_name { $($field $([ $method ])?),* });
+    };
+
+    // A node without a single AST type to read it from, converted by hand.
+    {
+        @node $struct_name:ident { $($field:ident: $field_type:ty),* $(,)? }
+    } => {
+        #[derive(Clone, Debug)]
+        #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
         pub struct $struct_name {
-            $(pub $field: $field_type),*
+            $(pub $field: $field_type,)*
+            /// Source location, `None` for nodes built programmatically.
+            #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+            pub span: Option<$crate::Span>,
         }
 
-        impl<'db> $crate::FromAst<'db, cairo_lang_syntax::node::ast::$ast_type<'db>> for $struct_name {
-            fn from_ast(ast: cairo_lang_syntax::node::ast::$ast_type<'db>, db: &'db dyn salsa::Database) -> Self {
-                $struct_name {
-                    $(
-                        $field: $crate::syntax_type!(@get_value ast, db, $field $(, $method)?),
-                    )*
-                }
+        // Spans are ignored so parsed and generated nodes compare equal.
+        impl PartialEq for $struct_name {
+            fn eq(&self, other: &Self) -> bool {
+                true $(&& self.$field == other.$field)*
             }
         }
 
-        $crate::syntax_display!($struct_name);
+        impl Eq for $struct_name {}
+
+        impl std::hash::Hash for $struct_name {
+            fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+                $(self.$field.hash(state);)*
+            }
+        }
+
+        impl $crate::Spanned for $struct_name {
+            fn span(&self) -> Option<$crate::Span> {
+                self.span
+            }
+        }
+
+        impl $crate::visit::Visitable for $struct_name {
+            fn accept<V: $crate::visit::Visit + ?Sized>(&self, visitor: &mut V) {
+                paste::paste! { visitor.[<visit_ $struct_name:snake>](self) }
+            }
+
+            fn walk<V: $crate::visit::Visit + ?Sized>(&self, visitor: &mut V) {
+                visitor.visit_span(&self.span);
+                $($crate::visit::Visitable::accept(&self.$field, visitor);)*
+            }
+
+            fn accept_mut<V: $crate::visit::VisitMut + ?Sized>(&mut self, visitor: &mut V) {
+                paste::paste! { visitor.[<visit_ $struct_name:snake _mut>](self) }
+            }
+
+            fn walk_mut<V: $crate::visit::VisitMut + ?Sized>(&mut self, visitor: &mut V) {
+                visitor.visit_span_mut(&mut self.span);
+                $($crate::visit::Visitable::accept_mut(&mut self.$field, visitor);)*
+            }
+
+            fn fold<F: $crate::visit::Fold + ?Sized>(self, folder: &mut F) -> Self {
+                paste::paste! { folder.[<fold_ $struct_name:snake>](self) }
+            }
 
+            fn fold_children<F: $crate::visit::Fold + ?Sized>(self, folder: &mut F) -> Self {
+                $struct_name {
+                    $($field: $crate::visit::Visitable::fold(self.$field, folder),)*
+                    span: self.span,
+                }
+            }
+        }
 
         // Auto-implement traits based on field names
         $($crate::syntax_type!(@impl_trait_for_field $struct_name, $field, $field_type);)*
     };
 
-    // Without AST type override - use struct name
     {
-        $struct_name:ident { $($field:ident $([ $method:ident ])?: $field_type:ty),* $(,)? }
+        @from_ast $struct_name:ident, $ast_type:ident { $($field:ident $([ $method:ident ])?),* }
     } => {
-        #[derive(Clone, Debug, PartialEq)]
-        pub struct $struct_name {
-            $(pub $field: $field_type),*
-        }
-
-        impl<'db> $crate::FromAst<'db, cairo_lang_syntax::node::ast::$struct_name<'db>> for $struct_name {
-            fn from_ast(ast: cairo_lang_syntax::node::ast::$struct_name<'db>, db: &'db dyn salsa::Database) -> Self {
+        impl<'db> $crate::FromAst<'db, cairo_lang_syntax::node::ast::$ast_type<'db>> for $struct_name {
+            fn from_ast(ast: cairo_lang_syntax::node::ast::$ast_type<'db>, db: &'db dyn salsa::Database) -> Self {
                 $struct_name {
                     $(
                         $field: $crate::syntax_type!(@get_value ast, db, $field $(, $method)?),
                     )*
+                    span: Some($crate::span::node_span(&ast, db)),
                 }
             }
         }
 
-        // Auto-implement traits based on field names
-        $($crate::syntax_type!(@impl_trait_for_field $struct_name, $field, $field_type);)*
+        impl<'db> $crate::TryFromAst<'db, cairo_lang_syntax::node::ast::$ast_type<'db>> for $struct_name {
+            fn try_from_ast(ast: cairo_lang_syntax::node::ast::$ast_type<'db>, db: &'db dyn salsa::Database) -> $crate::AstResult<Self> {
+                $crate::ast::checked_from_ast(ast, db)
+            }
+        }
+    };
+
+    (@get_value $ast:ident, $db:ident, docs) => {
+        $crate::ast::doc_comments(cairo_lang_syntax::node::TypedSyntaxNode::as_syntax_node(&$ast), $db)
     };
 
     (@get_value $ast:ident, $db:ident, $field:ident, $method:ident) => {
-        crate::AstInto::ast_into($ast.$method($db), $db)
+        $crate::AstInto::ast_into($ast.$method($db), $db)
     };
 
     (@get_value $ast:ident, $db:ident, $field:ident) => {
-        crate::AstInto::ast_into($ast.$field($db), $db)
+        $crate::AstInto::ast_into($ast.$field($db), $db)
     };
 
     // Check if field is "name" and implement NameTrait
@@ -201,6 +271,19 @@ macro_rules! syntax_type {
     };
 
 
+    // Check if field is "docs" and implement DocsTrait
+    (@impl_trait_for_field $struct_name:ident, docs, $field_type:ty) => {
+        impl $crate::DocsTrait for $struct_name {
+            fn docs(&self) -> &[String] {
+                &self.docs
+            }
+
+            fn docs_mut(&mut self) -> &mut Vec<String> {
+                &mut self.docs
+            }
+        }
+    };
+
     // Check if field is "generic_params" and implement GenericParamsTrait
     (@impl_trait_for_field $struct_name:ident, generic_params, $field_type:ty) => {
         impl $crate::GenericParamsTrait for $struct_name {
@@ -237,26 +320,59 @@ macro_rules! syntax_terminal_enum {
     // Implementation
     (@impl $enum_name:ident, $ast_enum:ident, { $($variant:ident $([ $terminal:ident ])?),* }) => {
         paste::paste! {
-            #[derive(Clone, Debug, PartialEq)]
+            #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+            #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
             pub enum $enum_name {
                 $($variant),*
             }
 
             $crate::syntax_display!($enum_name);
 
+            impl $crate::visit::Visitable for $enum_name {
+                fn accept<V: $crate::visit::Visit + ?Sized>(&self, visitor: &mut V) {
+                    visitor.[<visit_ $enum_name:snake>](self)
+                }
+
+                fn walk<V: $crate::visit::Visit + ?Sized>(&self, _visitor: &mut V) {}
+
+                fn accept_mut<V: $crate::visit::VisitMut + ?Sized>(&mut self, visitor: &mut V) {
+                    visitor.[<visit_ $enum_name:snake _mut>](self)
+                }
+
+                fn walk_mut<V: $crate::visit::VisitMut + ?Sized>(&mut self, _visitor: &mut V) {}
+
+                fn fold<F: $crate::visit::Fold + ?Sized>(self, folder: &mut F) -> Self {
+                    folder.[<fold_ $enum_name:snake>](self)
+                }
+
+                fn fold_children<F: $crate::visit::Fold + ?Sized>(self, _folder: &mut F) -> Self {
+                    self
+                }
+            }
+
             impl<'db> $crate::FromAst<'db, cairo_lang_syntax::node::ast::$ast_enum<'db>> for $enum_name {
                 fn from_ast(ast: cairo_lang_syntax::node::ast::$ast_enum<'db>, _db: &'db dyn salsa::Database) -> Self {
                     From::from(ast)
                 }
 
                 fn from_syntax_node(db: &'db dyn salsa::Database, node: cairo_lang_syntax::node::SyntaxNode<'db>) -> Self {
+                    <Self as $crate::TryFromAst<'db, cairo_lang_syntax::node::ast::$ast_enum<'db>>>::try_from_syntax_node(db, node)
+                        .unwrap_or_else(|err| panic!("{err}"))
+                }
+            }
+
+            impl<'db> $crate::TryFromAst<'db, cairo_lang_syntax::node::ast::$ast_enum<'db>> for $enum_name {
+                fn try_from_ast(ast: cairo_lang_syntax::node::ast::$ast_enum<'db>, _db: &'db dyn salsa::Database) -> $crate::AstResult<Self> {
+                    Ok(From::from(ast))
+                }
+
+                fn try_from_syntax_node(db: &'db dyn salsa::Database, node: cairo_lang_syntax::node::SyntaxNode<'db>) -> $crate::AstResult<Self> {
                     use cairo_lang_syntax::node::kind::SyntaxKind;
-                    let kind = node.kind(db);
-                    match kind {
+                    match node.kind(db) {
                         $(
-                            $crate::syntax_terminal_enum!(@terminal_kind $variant $(, $terminal)?) => $enum_name::$variant,
+                            $crate::syntax_terminal_enum!(@terminal_kind $variant $(, $terminal)?) => Ok($enum_name::$variant),
                         )*
-                        _ => panic!("Node {:?} Not a {}", kind, stringify!($ast_enum)),
+                        _ => Err($crate::AstError::unexpected_kind(db, node, stringify!($ast_enum))),
                     }
                 }
             }
@@ -301,7 +417,8 @@ macro_rules! syntax_enum {
             $( ( $ty:ty ) )?
         ),* $(,)?
     }) => {
-        #[derive(Clone, Debug, PartialEq)]
+        #[derive(Clone, Debug, PartialEq, Eq, Hash)]
+        #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
         pub enum $enum_name {
             $( $variant $( ( $ty ) )?, )*
         }
@@ -320,9 +437,87 @@ macro_rules! syntax_enum {
             }
         }
 
+        impl<'db> $crate::TryFromAst<'db, cairo_lang_syntax::node::ast::$ast_enum<'db>> for $enum_name {
+            fn try_from_ast(
+                ast: cairo_lang_syntax::node::ast::$ast_enum<'db>,
+                db: &'db dyn salsa::Database,
+            ) -> $crate::AstResult<Self> {
+                $crate::ast::checked_from_ast(ast, db)
+            }
+        }
+
+        impl $crate::visit::Visitable for $enum_name {
+            fn accept<V: $crate::visit::Visit + ?Sized>(&self, visitor: &mut V) {
+                paste::paste! { visitor.[<visit_ $enum_name:snake>](self) }
+            }
+
+            fn walk<V: $crate::visit::Visit + ?Sized>(&self, visitor: &mut V) {
+                match self {
+                    $(
+                        syntax_enum!(@walk_pat $variant $( ( $ty ) )? __e)
+                            => syntax_enum!(@walk_expr $( ( $ty ) )? __e, visitor),
+                    )*
+                }
+            }
+
+            fn accept_mut<V: $crate::visit::VisitMut + ?Sized>(&mut self, visitor: &mut V) {
+                paste::paste! { visitor.[<visit_ $enum_name:snake _mut>](self) }
+            }
+
+            fn walk_mut<V: $crate::visit::VisitMut + ?Sized>(&mut self, visitor: &mut V) {
+                match self {
+                    $(
+                        syntax_enum!(@walk_pat $variant $( ( $ty ) )? __e)
+                            => syntax_enum!(@walk_mut_expr $( ( $ty ) )? __e, visitor),
+                    )*
+                }
+            }
+
+            fn fold<F: $crate::visit::Fold + ?Sized>(self, folder: &mut F) -> Self {
+                paste::paste! { folder.[<fold_ $enum_name:snake>](self) }
+            }
+
+            fn fold_children<F: $crate::visit::Fold + ?Sized>(self, folder: &mut F) -> Self {
+                match self {
+                    $(
+                        syntax_enum!(@walk_pat $variant $( ( $ty ) )? __e)
+                            => syntax_enum!(@fold_expr $variant $( ( $ty ) )? __e, folder),
+                    )*
+                }
+            }
+        }
+
         $crate::syntax_display!($enum_name);
     };
 
+    (@walk_pat $variant:ident ( $ty:ty ) $e:ident) => {
+        Self::$variant($e)
+    };
+    (@walk_pat $variant:ident $e:ident) => {
+        Self::$variant
+    };
+
+    (@walk_expr ( $ty:ty ) $e:ident, $visitor:ident) => {
+        $crate::visit::Visitable::accept($e, $visitor)
+    };
+    (@walk_expr $e:ident, $visitor:ident) => {
+        {}
+    };
+
+    (@walk_mut_expr ( $ty:ty ) $e:ident, $visitor:ident) => {
+        $crate::visit::Visitable::accept_mut($e, $visitor)
+    };
+    (@walk_mut_expr $e:ident, $visitor:ident) => {
+        {}
+    };
+
+    (@fold_expr $variant:ident ( $ty:ty ) $e:ident, $folder:ident) => {
+        Self::$variant($crate::visit::Visitable::fold($e, $folder))
+    };
+    (@fold_expr $variant:ident $e:ident, $folder:ident) => {
+        Self::$variant
+    };
+
     (@pat $ast_enum:ident $variant:ident [ $ast_variant:ident ] ( $ty:ty ) $e:ident) => {
         cairo_lang_syntax::node::ast::$ast_enum::$ast_variant($e)
     };
@@ -361,12 +556,26 @@ macro_rules! syntax_terminal_bool {
                     }
                 }
                 fn from_syntax_node(db: &'db dyn salsa::Database, node: cairo_lang_syntax::node::SyntaxNode<'db>) -> Self {
+                    <Self as $crate::TryFromAst<'db, cairo_lang_syntax::node::ast::[<OptionTerminal $ast_type>]<'db>>>::try_from_syntax_node(db, node)
+                        .unwrap_or_else(|err| panic!("{err}"))
+                }
+            }
+
+            impl<'db> $crate::TryFromAst<'db, cairo_lang_syntax::node::ast::[<OptionTerminal $ast_type>]<'db>>
+                for bool
+            {
+                fn try_from_ast(
+                    ast: cairo_lang_syntax::node::ast::[<OptionTerminal $ast_type>]<'db>,
+                    db: &'db dyn salsa::Database,
+                ) -> $crate::AstResult<Self> {
+                    Ok($crate::FromAst::from_ast(ast, db))
+                }
+                fn try_from_syntax_node(db: &'db dyn salsa::Database, node: cairo_lang_syntax::node::SyntaxNode<'db>) -> $crate::AstResult<Self> {
                     use cairo_lang_syntax::node::kind::SyntaxKind;
-                    let kind = node.kind(db);
-                    match kind {
-                        SyntaxKind::[<Terminal $ast_type>] => true,
-                        SyntaxKind::[<OptionTerminal $ast_type Empty>] => false,
-                        _ => panic!("Node {:?} Not an OptionTerminal{}", kind, stringify!($ast_type)),
+                    match node.kind(db) {
+                        SyntaxKind::[<Terminal $ast_type>] => Ok(true),
+                        SyntaxKind::[<OptionTerminal $ast_type Empty>] => Ok(false),
+                        _ => Err($crate::AstError::unexpected_kind(db, node, stringify!([<OptionTerminal $ast_type>]))),
                     }
                 }
             }
@@ -397,6 +606,15 @@ macro_rules! syntax_option {
                     }
                 }
             }
+
+            impl<'db> $crate::TryFromAst<'db, cairo_lang_syntax::node::ast::$ast_type<'db>> for Option<$inner_type> {
+                fn try_from_ast(
+                    ast: cairo_lang_syntax::node::ast::$ast_type<'db>,
+                    db: &'db dyn salsa::Database
+                ) -> $crate::AstResult<Self> {
+                    $crate::ast::checked_from_ast(ast, db)
+                }
+            }
         }
     };
 
@@ -412,7 +630,7 @@ macro_rules! syntax_option {
                     ast: cairo_lang_syntax::node::ast::$ast_type<'db>,
                     db: &'db dyn salsa::Database
                 ) -> Self {
-                    use crate::AstInto;
+                    use $crate::AstInto;
                     match ast {
                         cairo_lang_syntax::node::ast::$ast_type::[<$some_variant>](val) =>
                             Some(val.ast_into(db)),
@@ -421,6 +639,15 @@ macro_rules! syntax_option {
                     }
                 }
             }
+
+            impl<'db> $crate::TryFromAst<'db, cairo_lang_syntax::node::ast::$ast_type<'db>> for Option<$inner_type> {
+                fn try_from_ast(
+                    ast: cairo_lang_syntax::node::ast::$ast_type<'db>,
+                    db: &'db dyn salsa::Database
+                ) -> $crate::AstResult<Self> {
+                    $crate::ast::checked_from_ast(ast, db)
+                }
+            }
         }
     };
 }
@@ -0,0 +1,108 @@
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+use cairo_lang_diagnostics::{DiagnosticEntry, Diagnostics, Severity as CairoSeverity};
+use cairo_lang_parser::ParserDiagnostic;
+use salsa::Database;
+use thiserror::Error;
+
+use crate::Span;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+/// A parser diagnostic detached from the parser database.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ParseDiagnostic {
+    pub message: String,
+    pub severity: Severity,
+    pub span: Span,
+}
+
+#[derive(Debug, Error)]
+pub enum ParseError {
+    #[error("{}", DisplayDiagnostics(.0))]
+    Diagnostics(Vec<ParseDiagnostic>),
+    #[error("no item found in input")]
+    NoItem,
+    #[error("expected {0}")]
+    Expected(&'static str),
+    #[error("invalid quote template: {0}")]
+    Quote(String),
+}
+
+pub type ParseResult<T> = Result<T, ParseError>;
+
+impl From<CairoSeverity> for Severity {
+    fn from(severity: CairoSeverity) -> Self {
+        match severity {
+            CairoSeverity::Error => Severity::Error,
+            CairoSeverity::Warning => Severity::Warning,
+        }
+    }
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Severity::Error => f.write_str("error"),
+            Severity::Warning => f.write_str("warning"),
+        }
+    }
+}
+
+impl Display for ParseDiagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(
+            f,
+            "{}: {} ({}..{})",
+            self.severity, self.message, self.span.start, self.span.end
+        )
+    }
+}
+
+struct DisplayDiagnostics<'a>(&'a [ParseDiagnostic]);
+
+impl Display for DisplayDiagnostics<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let mut diagnostics = self.0.iter();
+        if let Some(first) = diagnostics.next() {
+            first.fmt(f)?;
+        }
+        diagnostics.try_for_each(|d| write!(f, "\n{d}"))
+    }
+}
+
+impl ParseDiagnostic {
+    pub fn is_error(&self) -> bool {
+        self.severity == Severity::Error
+    }
+}
+
+pub fn collect_diagnostics<'db>(
+    db: &'db dyn Database,
+    diagnostics: Diagnostics<'db, ParserDiagnostic<'db>>,
+) -> Vec<ParseDiagnostic> {
+    diagnostics
+        .get_diagnostics_without_duplicates(db)
+        .into_iter()
+        .map(|d| ParseDiagnostic {
+            message: d.format(db),
+            severity: d.severity().into(),
+            span: d.span.into(),
+        })
+        .collect()
+}
+
+/// Returns `value` only if the parser emitted no diagnostics at all.
+pub fn strict<T>((value, diagnostics): (T, Vec<ParseDiagnostic>)) -> ParseResult<T> {
+    if diagnostics.is_empty() {
+        Ok(value)
+    } else {
+        Err(ParseError::Diagnostics(diagnostics))
+    }
+}
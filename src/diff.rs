@@ -0,0 +1,506 @@
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+use crate::item::{
+    Constant, Enum, ExternFunction, ExternType, FunctionDeclaration, FunctionWithBody, Impl,
+    ImplAlias, ImplItem, InlineMacroItem, Member, Module, Struct, Trait, TraitConstant,
+    TraitFunction, TraitImpl, TraitItem, TraitType, TypeAlias, UseItem, Variant,
+};
+use crate::macros::MacroDeclarationItem;
+use crate::{
+    Attribute, CairoFormat, CairoWrite, Expr, ExprPath, GenericParam, Item, NameTrait, Param,
+    Pattern, Statement, Type,
+};
+
+/// A single difference between two trees. The path locates it from the root, for example
+/// `["Struct Position", "member vec", "type"]`, and nodes are rendered as Cairo code.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Edit {
+    Added {
+        path: Vec<String>,
+        code: String,
+    },
+    Removed {
+        path: Vec<String>,
+        code: String,
+    },
+    Changed {
+        path: Vec<String>,
+        old: String,
+        new: String,
+    },
+}
+
+impl Edit {
+    pub fn path(&self) -> &[String] {
+        match self {
+            Edit::Added { path, .. } | Edit::Removed { path, .. } | Edit::Changed { path, .. } => {
+                path
+            }
+        }
+    }
+}
+
+/// The edits turning one tree into another, see [`diff`]. Displays as a readable summary with
+/// one entry per edit.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AstDiff {
+    pub edits: Vec<Edit>,
+}
+
+impl AstDiff {
+    pub fn is_empty(&self) -> bool {
+        self.edits.is_empty()
+    }
+}
+
+/// Compares two nodes, spans are ignored.
+pub fn diff<T: Diffable + ?Sized>(old: &T, new: &T) -> AstDiff {
+    let mut differ = Differ::default();
+    old.diff_into(new, &mut differ);
+    AstDiff {
+        edits: differ.edits,
+    }
+}
+
+/// Nodes that can be compared by [`diff`]. Items and their bodies are matched by kind and name
+/// so that changes are reported on the member, variant or nested item that changed, other nodes
+/// are reported as a whole.
+pub trait Diffable {
+    fn diff_into(&self, new: &Self, differ: &mut Differ);
+}
+
+/// Collects edits while walking two trees, keeping track of the current path.
+#[derive(Default)]
+pub struct Differ {
+    path: Vec<String>,
+    edits: Vec<Edit>,
+}
+
+impl Differ {
+    pub fn added(&mut self, code: String) {
+        let path = self.path.clone();
+        self.edits.push(Edit::Added { path, code });
+    }
+
+    pub fn removed(&mut self, code: String) {
+        let path = self.path.clone();
+        self.edits.push(Edit::Removed { path, code });
+    }
+
+    pub fn changed(&mut self, old: String, new: String) {
+        let path = self.path.clone();
+        self.edits.push(Edit::Changed { path, old, new });
+    }
+
+    /// Reports the node as changed if it differs.
+    pub fn whole<T: CairoWrite + PartialEq>(&mut self, old: &T, new: &T) {
+        if old != new {
+            self.changed(code(old), code(new));
+        }
+    }
+
+    /// Diffs `old` and `new` under the path segment `name`.
+    pub fn nested<T: Diffable + ?Sized>(&mut self, name: impl Into<String>, old: &T, new: &T) {
+        self.path.push(name.into());
+        old.diff_into(new, self);
+        self.path.pop();
+    }
+
+    /// Reports the field as changed if it differs, rendering both sides with `render`.
+    pub fn field<T: PartialEq + ?Sized>(
+        &mut self,
+        name: &str,
+        old: &T,
+        new: &T,
+        render: impl Fn(&T) -> String,
+    ) {
+        if old != new {
+            self.path.push(name.to_string());
+            self.changed(render(old), render(new));
+            self.path.pop();
+        }
+    }
+
+    fn docs(&mut self, old: &[String], new: &[String]) {
+        self.field("docs", old, new, |docs| docs.join("\n"));
+    }
+
+    fn name(&mut self, old: &str, new: &str) {
+        self.field("name", old, new, str::to_string);
+    }
+
+    fn attributes(&mut self, old: &Vec<Attribute>, new: &Vec<Attribute>) {
+        self.field("attributes", old, new, code);
+    }
+
+    fn generic_params(&mut self, old: &Option<Vec<GenericParam>>, new: &Option<Vec<GenericParam>>) {
+        self.field("generic_params", old, new, code);
+    }
+
+    /// Matches the elements of both lists by key, reporting unmatched ones as removed or added
+    /// and a change of order among the matched ones.
+    fn keyed<T: Keyed + Diffable + CairoWrite + PartialEq>(&mut self, old: &[T], new: &[T]) {
+        let (old_keys, new_keys) = (unique_keys(old), unique_keys(new));
+        let new_index: HashMap<&str, usize> = new_keys
+            .iter()
+            .enumerate()
+            .map(|(i, key)| (key.as_str(), i))
+            .collect();
+        let mut matched = Vec::new();
+        for (node, key) in old.iter().zip(&old_keys) {
+            match new_index.get(key.as_str()) {
+                Some(&i) => {
+                    matched.push(key.as_str());
+                    self.nested(key.clone(), node, &new[i]);
+                }
+                None => {
+                    self.path.push(key.clone());
+                    self.removed(code(node));
+                    self.path.pop();
+                }
+            }
+        }
+        for (node, key) in new.iter().zip(&new_keys) {
+            if !old_keys.contains(key) {
+                self.path.push(key.clone());
+                self.added(code(node));
+                self.path.pop();
+            }
+        }
+        let reordered: Vec<&str> = new_keys
+            .iter()
+            .map(String::as_str)
+            .filter(|key| matched.contains(key))
+            .collect();
+        if matched != reordered {
+            self.path.push("order".to_string());
+            self.changed(matched.join(", "), reordered.join(", "));
+            self.path.pop();
+        }
+    }
+
+    fn keyed_body<T: Keyed + Diffable + CairoWrite + PartialEq>(
+        &mut self,
+        old: &Option<Vec<T>>,
+        new: &Option<Vec<T>>,
+    ) {
+        match (old, new) {
+            (Some(old), Some(new)) => self.keyed(old, new),
+            _ => self.field("body", old, new, |body| match body {
+                Some(nodes) => nodes.iter().map(code).collect::<Vec<_>>().join("\n"),
+                None => ";".to_string(),
+            }),
+        }
+    }
+}
+
+fn code<T: CairoWrite>(node: &T) -> String {
+    node.pretty().trim_end().to_string()
+}
+
+/// Identifies an element of a list across both trees.
+trait Keyed {
+    fn key(&self) -> String;
+}
+
+/// Keys of the elements, repeated keys get a `#n` suffix in order of appearance.
+fn unique_keys<T: Keyed>(nodes: &[T]) -> Vec<String> {
+    let mut seen: HashMap<String, usize> = HashMap::new();
+    nodes
+        .iter()
+        .map(|node| {
+            let key = node.key();
+            let count = seen.entry(key.clone()).or_default();
+            *count += 1;
+            match count {
+                1 => key,
+                n => format!("{key} #{n}"),
+            }
+        })
+        .collect()
+}
+
+impl Keyed for Item {
+    fn key(&self) -> String {
+        match (self, self.name()) {
+            (_, Some(name)) => format!("{} {name}", self.kind()),
+            (Item::InlineMacro(e), None) => format!("{} {}!", self.kind(), e.path.stringify()),
+            (Item::Use(e), None) => format!("{} {}", self.kind(), e.path.stringify()),
+            (_, None) => self.kind().to_string(),
+        }
+    }
+}
+
+impl Keyed for TraitItem {
+    fn key(&self) -> String {
+        match self {
+            TraitItem::Function(e) => format!("TraitFunction {}", e.declaration.name),
+            TraitItem::Type(e) => format!("TraitType {}", e.name),
+            TraitItem::Constant(e) => format!("TraitConstant {}", e.name),
+            TraitItem::Impl(e) => format!("TraitImpl {}", e.name),
+            TraitItem::Missing => "Missing".to_string(),
+        }
+    }
+}
+
+impl Keyed for ImplItem {
+    fn key(&self) -> String {
+        match self {
+            ImplItem::Function(e) => format!("FunctionWithBody {}", e.declaration.name),
+            ImplItem::Type(e) => format!("TypeAlias {}", e.name),
+            ImplItem::Constant(e) => format!("Constant {}", e.name),
+            ImplItem::Impl(e) => format!("ImplAlias {}", e.name),
+            ImplItem::Module(e) => format!("Module {}", e.name),
+            ImplItem::Use(e) => format!("UseItem {}", e.path.stringify()),
+            ImplItem::ExternFunction(e) => format!("ExternFunction {}", e.declaration.name),
+            ImplItem::ExternType(e) => format!("ExternType {}", e.name),
+            ImplItem::Trait(e) => format!("Trait {}", e.name),
+            ImplItem::Struct(e) => format!("Struct {}", e.name),
+            ImplItem::Enum(e) => format!("Enum {}", e.name),
+            ImplItem::Missing => "Missing".to_string(),
+        }
+    }
+}
+
+impl Keyed for Member {
+    fn key(&self) -> String {
+        format!("member {}", self.name())
+    }
+}
+
+impl Keyed for Variant {
+    fn key(&self) -> String {
+        format!("variant {}", self.name())
+    }
+}
+
+impl Diffable for Vec<Item> {
+    fn diff_into(&self, new: &Self, differ: &mut Differ) {
+        differ.keyed(self, new);
+    }
+}
+
+impl Diffable for Item {
+    fn diff_into(&self, new: &Self, differ: &mut Differ) {
+        match (self, new) {
+            (Item::Constant(old), Item::Constant(new)) => old.diff_into(new, differ),
+            (Item::Module(old), Item::Module(new)) => old.diff_into(new, differ),
+            (Item::Use(old), Item::Use(new)) => old.diff_into(new, differ),
+            (Item::FreeFunction(old), Item::FreeFunction(new)) => old.diff_into(new, differ),
+            (Item::ExternFunction(old), Item::ExternFunction(new)) => old.diff_into(new, differ),
+            (Item::ExternType(old), Item::ExternType(new)) => old.diff_into(new, differ),
+            (Item::Trait(old), Item::Trait(new)) => old.diff_into(new, differ),
+            (Item::Impl(old), Item::Impl(new)) => old.diff_into(new, differ),
+            (Item::ImplAlias(old), Item::ImplAlias(new)) => old.diff_into(new, differ),
+            (Item::Struct(old), Item::Struct(new)) => old.diff_into(new, differ),
+            (Item::Enum(old), Item::Enum(new)) => old.diff_into(new, differ),
+            (Item::TypeAlias(old), Item::TypeAlias(new)) => old.diff_into(new, differ),
+            (Item::InlineMacro(old), Item::InlineMacro(new)) => old.diff_into(new, differ),
+            (Item::MacroDeclaration(old), Item::MacroDeclaration(new)) => {
+                old.diff_into(new, differ)
+            }
+            (old, new) => differ.whole(old, new),
+        }
+    }
+}
+
+impl Diffable for TraitItem {
+    fn diff_into(&self, new: &Self, differ: &mut Differ) {
+        match (self, new) {
+            (TraitItem::Function(old), TraitItem::Function(new)) => old.diff_into(new, differ),
+            (old, new) => differ.whole(old, new),
+        }
+    }
+}
+
+impl Diffable for ImplItem {
+    fn diff_into(&self, new: &Self, differ: &mut Differ) {
+        match (self, new) {
+            (ImplItem::Function(old), ImplItem::Function(new)) => old.diff_into(new, differ),
+            (ImplItem::Module(old), ImplItem::Module(new)) => old.diff_into(new, differ),
+            (ImplItem::Trait(old), ImplItem::Trait(new)) => old.diff_into(new, differ),
+            (ImplItem::Struct(old), ImplItem::Struct(new)) => old.diff_into(new, differ),
+            (ImplItem::Enum(old), ImplItem::Enum(new)) => old.diff_into(new, differ),
+            (old, new) => differ.whole(old, new),
+        }
+    }
+}
+
+impl Diffable for Struct {
+    fn diff_into(&self, new: &Self, differ: &mut Differ) {
+        differ.docs(&self.docs, &new.docs);
+        differ.attributes(&self.attributes, &new.attributes);
+        differ.name(&self.name, &new.name);
+        differ.field("visibility", &self.visibility, &new.visibility, code);
+        differ.generic_params(&self.generic_params, &new.generic_params);
+        differ.keyed(&self.members, &new.members);
+    }
+}
+
+impl Diffable for Member {
+    fn diff_into(&self, new: &Self, differ: &mut Differ) {
+        differ.docs(&self.docs, &new.docs);
+        differ.attributes(&self.attributes, &new.attributes);
+        differ.name(&self.name, &new.name);
+        differ.field("visibility", &self.visibility, &new.visibility, code);
+        differ.field("type", &self.ty, &new.ty, code);
+    }
+}
+
+impl Diffable for Enum {
+    fn diff_into(&self, new: &Self, differ: &mut Differ) {
+        differ.docs(&self.docs, &new.docs);
+        differ.attributes(&self.attributes, &new.attributes);
+        differ.name(&self.name, &new.name);
+        differ.field("visibility", &self.visibility, &new.visibility, code);
+        differ.generic_params(&self.generic_params, &new.generic_params);
+        differ.keyed(&self.variants, &new.variants);
+    }
+}
+
+impl Diffable for Variant {
+    fn diff_into(&self, new: &Self, differ: &mut Differ) {
+        differ.docs(&self.docs, &new.docs);
+        differ.attributes(&self.attributes, &new.attributes);
+        differ.name(&self.name, &new.name);
+        differ.field("type", &self.type_clause, &new.type_clause, |ty| {
+            ty.as_ref().map_or_else(String::new, code)
+        });
+    }
+}
+
+impl Diffable for Module {
+    fn diff_into(&self, new: &Self, differ: &mut Differ) {
+        differ.docs(&self.docs, &new.docs);
+        differ.attributes(&self.attributes, &new.attributes);
+        differ.name(&self.name, &new.name);
+        differ.field("visibility", &self.visibility, &new.visibility, code);
+        differ.keyed_body(&self.body, &new.body);
+    }
+}
+
+impl Diffable for Trait {
+    fn diff_into(&self, new: &Self, differ: &mut Differ) {
+        differ.docs(&self.docs, &new.docs);
+        differ.attributes(&self.attributes, &new.attributes);
+        differ.name(&self.name, &new.name);
+        differ.field("visibility", &self.visibility, &new.visibility, code);
+        differ.generic_params(&self.generic_params, &new.generic_params);
+        differ.keyed_body(&self.body, &new.body);
+    }
+}
+
+impl Diffable for Impl {
+    fn diff_into(&self, new: &Self, differ: &mut Differ) {
+        differ.docs(&self.docs, &new.docs);
+        differ.attributes(&self.attributes, &new.attributes);
+        differ.name(&self.name, &new.name);
+        differ.field("visibility", &self.visibility, &new.visibility, code);
+        differ.generic_params(&self.generic_params, &new.generic_params);
+        differ.field("trait_path", &self.trait_path, &new.trait_path, code);
+        differ.keyed_body(&self.body, &new.body);
+    }
+}
+
+impl Diffable for FunctionWithBody {
+    fn diff_into(&self, new: &Self, differ: &mut Differ) {
+        differ.docs(&self.docs, &new.docs);
+        differ.attributes(&self.attributes, &new.attributes);
+        differ.field("visibility", &self.visibility, &new.visibility, code);
+        differ.field("signature", &self.declaration, &new.declaration, code);
+        differ.field("body", &self.body, &new.body, code);
+    }
+}
+
+impl Diffable for TraitFunction {
+    fn diff_into(&self, new: &Self, differ: &mut Differ) {
+        differ.docs(&self.docs, &new.docs);
+        differ.attributes(&self.attributes, &new.attributes);
+        differ.field("signature", &self.declaration, &new.declaration, code);
+        differ.field("body", &self.body, &new.body, |body| {
+            body.as_ref().map_or_else(|| ";".to_string(), code)
+        });
+    }
+}
+
+macro_rules! diffable_as_whole {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl Diffable for $ty {
+                fn diff_into(&self, new: &Self, differ: &mut Differ) {
+                    differ.whole(self, new);
+                }
+            }
+        )*
+    };
+}
+
+diffable_as_whole! {
+    Constant,
+    UseItem,
+    ExternFunction,
+    ExternType,
+    ImplAlias,
+    TypeAlias,
+    InlineMacroItem,
+    MacroDeclarationItem,
+    TraitType,
+    TraitConstant,
+    TraitImpl,
+    FunctionDeclaration,
+    Vec<Statement>,
+    Statement,
+    Expr,
+    ExprPath,
+    Pattern,
+    Type,
+    Param,
+    Attribute,
+    GenericParam,
+}
+
+impl Display for Edit {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let path = match self.path() {
+            [] => "<root>".to_string(),
+            path => path.join(" > "),
+        };
+        match self {
+            Edit::Added { code, .. } => {
+                writeln!(f, "+ {path}")?;
+                write_lines(f, "+ ", code)
+            }
+            Edit::Removed { code, .. } => {
+                writeln!(f, "- {path}")?;
+                write_lines(f, "- ", code)
+            }
+            Edit::Changed { old, new, .. } => {
+                writeln!(f, "~ {path}")?;
+                // Only the lines between the common leading and trailing lines are shown.
+                let (old, new): (Vec<&str>, Vec<&str>) =
+                    (old.lines().collect(), new.lines().collect());
+                let prefix = old.iter().zip(&new).take_while(|(o, n)| o == n).count();
+                let suffix = old[prefix..]
+                    .iter()
+                    .rev()
+                    .zip(new[prefix..].iter().rev())
+                    .take_while(|(o, n)| o == n)
+                    .count();
+                write_lines(f, "- ", &old[prefix..old.len() - suffix].join("\n"))?;
+                write_lines(f, "+ ", &new[prefix..new.len() - suffix].join("\n"))
+            }
+        }
+    }
+}
+
+fn write_lines(f: &mut Formatter<'_>, marker: &str, code: &str) -> FmtResult {
+    code.lines()
+        .try_for_each(|line| writeln!(f, "    {marker}{line}"))
+}
+
+impl Display for AstDiff {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        self.edits.iter().try_for_each(|edit| edit.fmt(f))
+    }
+}
@@ -1,11 +1,16 @@
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
+
 use cairo_lang_syntax::node::ast;
 use salsa::Database;
 
 use super::{Condition, Param, Pattern, Statement};
+use crate::ast::checked_from_ast;
+use crate::span::node_span;
 use crate::{
-    Arg, AstInto, FromAst, from_typed_syntax_node, syntax_enum, syntax_option,
-    syntax_terminal_bool, syntax_terminal_enum, syntax_type,
-    typed_syntax_node_to_string_without_trivia, vec_from_element_list,
+    Arg, AstInto, AstResult, FromAst, ParseError, ParseResult, Span, Spanned, TryFromAst, Type,
+    from_typed_syntax_node, syntax_enum, syntax_option, syntax_terminal_bool, syntax_terminal_enum,
+    syntax_type, typed_syntax_node_to_string_without_trivia, vec_from_element_list,
 };
 syntax_enum! {
     Expr {
@@ -84,12 +89,44 @@ syntax_type! {
     }
 }
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct IfExpr {
     pub conditions: Vec<Condition>,
     pub if_block: Vec<Statement>,
     pub else_if_clauses: Vec<ElseIfBlock>,
     pub else_clause: Option<Vec<Statement>>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub span: Option<Span>,
+}
+
+impl PartialEq for IfExpr {
+    fn eq(&self, other: &Self) -> bool {
+        self.conditions == other.conditions
+            && self.if_block == other.if_block
+            && self.else_if_clauses == other.else_if_clauses
+            && self.else_clause == other.else_clause
+    }
+}
+
+impl Eq for IfExpr {}
+
+impl Hash for IfExpr {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.conditions.hash(state);
+        self.if_block.hash(state);
+        self.else_if_clauses.hash(state);
+        self.else_clause.hash(state);
+    }
+}
+
+impl Spanned for IfExpr {
+    fn span(&self) -> Option<Span> {
+        self.span
+    }
 }
 
 impl<'db> FromAst<'db, ast::ExprIf<'db>> for IfExpr {
@@ -101,10 +138,17 @@ impl<'db> FromAst<'db, ast::ExprIf<'db>> for IfExpr {
             if_block: ast.if_block(db).statements(db).ast_into(db),
             else_if_clauses,
             else_clause: else_block,
+            span: Some(node_span(&ast, db)),
         }
     }
 }
 
+impl<'db> TryFromAst<'db, ast::ExprIf<'db>> for IfExpr {
+    fn try_from_ast(ast: ast::ExprIf<'db>, db: &'db dyn Database) -> AstResult<Self> {
+        checked_from_ast(ast, db)
+    }
+}
+
 fn unpack_if_else_clause<'db>(
     clause: ast::OptionElseClause<'db>,
     clauses: &mut Vec<ElseIfBlock>,
@@ -175,7 +219,7 @@ syntax_type! {
 syntax_type! {
     GenericArgNamed {
         name: String,
-        value: Box<Expr>,
+        value: Box<Type>,
     }
 }
 
@@ -188,7 +232,7 @@ syntax_type! {
 syntax_type! {
     Closure[ExprClosure]{
         params: Vec<Param>,
-        ret_ty: Option<Box<Expr>>,
+        ret_ty: Option<Type>,
         no_panic[optional_no_panic]: bool,
         expr: Box<Expr>,
     }
@@ -202,19 +246,14 @@ syntax_type! {
 }
 
 from_typed_syntax_node! {StructArgTail.expression, Expr}
-from_typed_syntax_node! {ReturnTypeClause.ty, Expr}
-from_typed_syntax_node! {GenericArgUnnamed.value, Expr}
+from_typed_syntax_node! {GenericArgUnnamed.value, Type}
 from_typed_syntax_node! {ExprErrorPropagate.expr, Expr}
 from_typed_syntax_node! {ExprParenthesized.expr, Expr}
 from_typed_syntax_node! {StructArgExpr.expr, Expr}
 from_typed_syntax_node! {ExprClause.expr, Expr}
-from_typed_syntax_node! {TypeClause.ty, Expr}
 
 syntax_option! {OptionStructArgExpr {StructArgExpr: Expr}}
-syntax_option! {OptionReturnTypeClause{ReturnTypeClause: Expr}}
 syntax_option! {OptionFixedSizeArraySize {FixedSizeArraySize: Box<Expr>}}
-syntax_option! {OptionTypeClause {TypeClause: Expr}}
-syntax_option! {OptionReturnTypeClause {ReturnTypeClause: Box<Expr>}}
 syntax_option! {OptionExprClause{ExprClause: Expr}}
 
 // name only
@@ -250,7 +289,7 @@ syntax_enum! {
 
 syntax_enum!(
     GenericArg {
-        Unnamed(Expr),
+        Unnamed(Type),
         Named(GenericArgNamed),
     }
 );
@@ -303,3 +342,285 @@ syntax_terminal_enum! {
         Reference[TerminalAnd],
     }
 }
+
+/// Precedence of the postfix `?` and `[]` operators.
+pub const POSTFIX_PRECEDENCE: usize = 1;
+
+/// How a chain of operators with the same precedence groups.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Associativity {
+    /// `a - b - c` is `(a - b) - c`.
+    Left,
+    /// Chains such as `a < b < c` are rejected by the parser.
+    None,
+}
+
+impl BinaryOp {
+    /// Precedence as used by the Cairo parser, lower values bind tighter.
+    pub fn precedence(&self) -> usize {
+        match self {
+            BinaryOp::Dot | BinaryOp::Not => 0,
+            BinaryOp::Mul | BinaryOp::Div | BinaryOp::Mod => 2,
+            BinaryOp::Plus | BinaryOp::Minus => 3,
+            BinaryOp::And => 4,
+            BinaryOp::Xor => 5,
+            BinaryOp::Or => 6,
+            BinaryOp::EqEq
+            | BinaryOp::Neq
+            | BinaryOp::LT
+            | BinaryOp::GT
+            | BinaryOp::LE
+            | BinaryOp::GE => 7,
+            BinaryOp::AndAnd => 8,
+            BinaryOp::OrOr => 9,
+            BinaryOp::DotDot | BinaryOp::DotDotEq => 10,
+            BinaryOp::Eq
+            | BinaryOp::PlusEq
+            | BinaryOp::MinusEq
+            | BinaryOp::MulEq
+            | BinaryOp::DivEq
+            | BinaryOp::ModEq => 11,
+        }
+    }
+
+    pub fn associativity(&self) -> Associativity {
+        match self.precedence() {
+            7 => Associativity::None,
+            _ => Associativity::Left,
+        }
+    }
+}
+
+impl UnaryOp {
+    /// Prefix operators bind looser than `.`, `?` and `[]` but tighter than any binary operator,
+    /// `-a.b` is `-(a.b)`.
+    pub fn precedence(&self) -> usize {
+        2
+    }
+}
+
+impl Expr {
+    /// Precedence of the expression when used as an operand, `None` for expressions that
+    /// never need parentheses. Closures extend as far right as possible so they bind loosest.
+    pub fn precedence(&self) -> Option<usize> {
+        match self {
+            Expr::Binary(e) => Some(e.op.precedence()),
+            Expr::Unary(e) => Some(e.op.precedence()),
+            Expr::ErrorPropagate(_) | Expr::Indexed(_) => Some(POSTFIX_PRECEDENCE),
+            Expr::Closure(_) => Some(usize::MAX),
+            _ => None,
+        }
+    }
+
+    /// Whether the expression must be parenthesized as the left operand of `op`.
+    pub fn needs_parens_as_lhs(&self, op: &BinaryOp) -> bool {
+        match self.precedence() {
+            Some(precedence) if precedence == op.precedence() => {
+                op.associativity() == Associativity::None
+            }
+            Some(precedence) => precedence > op.precedence(),
+            None => false,
+        }
+    }
+
+    /// Whether the expression must be parenthesized as the right operand of `op`, prefix
+    /// operators are always parsed whole.
+    pub fn needs_parens_as_rhs(&self, op: &BinaryOp) -> bool {
+        match self {
+            Expr::Unary(_) => false,
+            _ => self.precedence() >= Some(op.precedence()),
+        }
+    }
+
+    /// Whether the expression must be parenthesized as the operand of a prefix operator.
+    pub fn needs_parens_as_unary_operand(&self) -> bool {
+        match self {
+            Expr::Unary(_) => false,
+            _ => self.precedence() > Some(POSTFIX_PRECEDENCE),
+        }
+    }
+
+    /// Whether the expression must be parenthesized before `?` or `[]`.
+    pub fn needs_parens_as_postfix_operand(&self) -> bool {
+        self.precedence() > Some(POSTFIX_PRECEDENCE)
+    }
+}
+
+impl PathSegment {
+    /// The identifier, `None` for a missing segment.
+    pub fn ident(&self) -> Option<&str> {
+        match self {
+            PathSegment::Simple(ident) => Some(ident),
+            PathSegment::WithGenerics(segment) => Some(&segment.ident),
+            PathSegment::Missing => None,
+        }
+    }
+
+    pub fn generic_args(&self) -> &[GenericArg] {
+        match self {
+            PathSegment::WithGenerics(segment) => &segment.args,
+            _ => &[],
+        }
+    }
+
+    pub fn without_generics(&self) -> PathSegment {
+        match self {
+            PathSegment::WithGenerics(segment) => PathSegment::Simple(segment.ident.clone()),
+            segment => segment.clone(),
+        }
+    }
+
+    /// Whether both segments have the same identifier, whatever their generic arguments.
+    pub fn eq_ignoring_generics(&self, other: &PathSegment) -> bool {
+        self.ident() == other.ident()
+    }
+}
+
+impl From<String> for PathSegment {
+    fn from(ident: String) -> Self {
+        PathSegment::Simple(ident)
+    }
+}
+
+impl From<&str> for PathSegment {
+    fn from(ident: &str) -> Self {
+        PathSegment::Simple(ident.to_string())
+    }
+}
+
+impl ExprPath {
+    /// A path without `$` made of `segments`.
+    pub fn new<S: Into<PathSegment>>(segments: impl IntoIterator<Item = S>) -> Self {
+        ExprPath {
+            dollar: false,
+            path: segments.into_iter().map(Into::into).collect(),
+            span: None,
+        }
+    }
+
+    /// The identifiers of the segments, skipping missing ones.
+    pub fn idents(&self) -> impl Iterator<Item = &str> {
+        self.path.iter().filter_map(PathSegment::ident)
+    }
+
+    pub fn first_ident(&self) -> Option<&str> {
+        self.path.first()?.ident()
+    }
+
+    pub fn last_ident(&self) -> Option<&str> {
+        self.path.last()?.ident()
+    }
+
+    /// The generic arguments of the last segment, as in `Array<T>`.
+    pub fn generic_args(&self) -> &[GenericArg] {
+        self.path.last().map_or(&[], PathSegment::generic_args)
+    }
+
+    /// The same path with the generic arguments of every segment removed.
+    pub fn without_generics(&self) -> ExprPath {
+        ExprPath {
+            dollar: self.dollar,
+            path: self
+                .path
+                .iter()
+                .map(PathSegment::without_generics)
+                .collect(),
+            span: self.span,
+        }
+    }
+
+    /// `self::other`, keeping the `$` of `self`.
+    pub fn join(&self, other: &ExprPath) -> ExprPath {
+        ExprPath {
+            dollar: self.dollar,
+            path: self.path.iter().chain(&other.path).cloned().collect(),
+            span: None,
+        }
+    }
+
+    pub fn push(&mut self, segment: impl Into<PathSegment>) {
+        self.path.push(segment.into());
+    }
+
+    /// Every segment but the last, `None` for a path of a single segment.
+    pub fn prefix(&self) -> Option<ExprPath> {
+        match self.path.split_last() {
+            Some((_, prefix)) if !prefix.is_empty() => Some(ExprPath {
+                dollar: self.dollar,
+                path: prefix.to_vec(),
+                span: None,
+            }),
+            _ => None,
+        }
+    }
+
+    /// Whether both paths name the same item, whatever their generic arguments.
+    pub fn eq_ignoring_generics(&self, other: &ExprPath) -> bool {
+        self.dollar == other.dollar && self.path.len() == other.path.len() && self.ends_with(other)
+    }
+
+    /// Whether the path ends with the segments of `suffix`, ignoring generic arguments, so
+    /// `core::array::Array<T>` ends with both `Array` and `array::Array`.
+    pub fn ends_with(&self, suffix: &ExprPath) -> bool {
+        self.path.len() >= suffix.path.len()
+            && self
+                .path
+                .iter()
+                .rev()
+                .zip(suffix.path.iter().rev())
+                .all(|(segment, other)| segment.eq_ignoring_generics(other))
+    }
+}
+
+impl FromStr for ExprPath {
+    type Err = ParseError;
+
+    /// Parses `path` in type position, as in `"core::array::Array<felt252>".parse()`.
+    fn from_str(path: &str) -> ParseResult<Self> {
+        <ExprPath as crate::Parse>::parse(path)
+    }
+}
+
+impl From<&str> for ExprPath {
+    /// Parses `path` in type position.
+    ///
+    /// # Panics
+    ///
+    /// If `path` is not a valid path, use [`FromStr`] for untrusted input.
+    fn from(path: &str) -> Self {
+        path.parse()
+            .unwrap_or_else(|error| panic!("invalid path `{path}`: {error}"))
+    }
+}
+
+impl Spanned for Expr {
+    fn span(&self) -> Option<Span> {
+        match self {
+            Expr::Path(e) => e.span(),
+            Expr::Parenthesized(e) | Expr::ErrorPropagate(e) => e.span(),
+            Expr::Unary(e) => e.span(),
+            Expr::Binary(e) => e.span(),
+            Expr::FunctionCall(e) => e.span(),
+            Expr::StructConstructorCall(e) => e.span(),
+            Expr::Match(e) => e.span(),
+            Expr::If(e) => e.span(),
+            Expr::Loop(e) => e.span(),
+            Expr::While(e) => e.span(),
+            Expr::For(e) => e.span(),
+            Expr::Closure(e) => e.span(),
+            Expr::Indexed(e) => e.span(),
+            Expr::InlineMacro(e) => e.span(),
+            Expr::FixedSizeArray(e) => e.span(),
+            Expr::Literal(_)
+            | Expr::ShortString(_)
+            | Expr::String(_)
+            | Expr::False
+            | Expr::True
+            | Expr::Tuple(_)
+            | Expr::Block(_)
+            | Expr::FieldInitShorthand(_)
+            | Expr::Underscore
+            | Expr::Missing => None,
+        }
+    }
+}
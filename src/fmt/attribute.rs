@@ -1,11 +1,11 @@
-use super::{CairoWrite, CairoWriteSlice};
+use super::{CairoWrite, CairoWriteSlice, CairoWriter};
 use crate::attribute::{Arg, ArgClause, Attribute, NamedArg};
-use std::fmt::{Result, Write};
+use std::fmt::Result;
 
 impl CairoWrite for Attribute {
-    fn cwrite<W: Write>(&self, buf: &mut W) -> Result {
+    fn cwrite<W: CairoWriter>(&self, buf: &mut W) -> Result {
         buf.write_str("#[")?;
-        self.path.cwrite(buf)?;
+        self.path().cwrite(buf)?;
         if let Some(arguments) = &self.arguments {
             arguments.cwrite_csv_parenthesized(buf)?;
         }
@@ -14,7 +14,7 @@ impl CairoWrite for Attribute {
 }
 
 impl CairoWrite for Vec<Attribute> {
-    fn cwrite<W: Write>(&self, buf: &mut W) -> Result {
+    fn cwrite<W: CairoWriter>(&self, buf: &mut W) -> Result {
         if !self.is_empty() {
             self.cwrite_terminated(buf, '\n')?;
         }
@@ -23,7 +23,7 @@ impl CairoWrite for Vec<Attribute> {
 }
 
 impl CairoWrite for Arg {
-    fn cwrite<W: Write>(&self, buf: &mut W) -> Result {
+    fn cwrite<W: CairoWriter>(&self, buf: &mut W) -> Result {
         self.modifiers.cwrite(buf)?;
         self.clause.cwrite(buf)?;
         Ok(())
@@ -31,7 +31,7 @@ impl CairoWrite for Arg {
 }
 
 impl CairoWrite for ArgClause {
-    fn cwrite<W: Write>(&self, buf: &mut W) -> Result {
+    fn cwrite<W: CairoWriter>(&self, buf: &mut W) -> Result {
         match self {
             ArgClause::Unnamed(expr) => expr.cwrite(buf),
             ArgClause::Named(a) => a.cwrite(buf),
@@ -41,7 +41,7 @@ impl CairoWrite for ArgClause {
 }
 
 impl CairoWrite for NamedArg {
-    fn cwrite<W: Write>(&self, buf: &mut W) -> Result {
+    fn cwrite<W: CairoWriter>(&self, buf: &mut W) -> Result {
         self.name.cwrite(buf)?;
         self.value.cwrite_prefixed_str(buf, ": ")
     }
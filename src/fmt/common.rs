@@ -1,16 +1,16 @@
-use super::{CairoWrite, CairoWriteSlice};
+use super::{CairoWrite, CairoWriteSlice, CairoWriter};
 use crate::common::{Identifier, Modifier, Param, Visibility};
-use std::fmt::{Result, Write};
+use std::fmt::Result;
 
 impl CairoWrite for Identifier {
-    fn cwrite<W: Write>(&self, buf: &mut W) -> Result {
+    fn cwrite<W: CairoWriter>(&self, buf: &mut W) -> Result {
         self.modifiers.cwrite(buf)?;
         self.name.cwrite(buf)
     }
 }
 
 impl CairoWrite for Modifier {
-    fn cwrite<W: Write>(&self, buf: &mut W) -> Result {
+    fn cwrite<W: CairoWriter>(&self, buf: &mut W) -> Result {
         match self {
             Modifier::Ref => buf.write_str("ref"),
             Modifier::Mut => buf.write_str("mut"),
@@ -19,13 +19,13 @@ impl CairoWrite for Modifier {
 }
 
 impl CairoWrite for Vec<Modifier> {
-    fn cwrite<W: Write>(&self, buf: &mut W) -> Result {
+    fn cwrite<W: CairoWriter>(&self, buf: &mut W) -> Result {
         self.cwrite_terminated(buf, ' ')
     }
 }
 
 impl CairoWrite for Param {
-    fn cwrite<W: Write>(&self, buf: &mut W) -> Result {
+    fn cwrite<W: CairoWriter>(&self, buf: &mut W) -> Result {
         self.modifiers.cwrite(buf)?;
         self.name.cwrite(buf)?;
         if let Some(type_clause) = &self.type_clause {
@@ -36,7 +36,7 @@ impl CairoWrite for Param {
 }
 
 impl CairoWrite for Visibility {
-    fn cwrite<W: Write>(&self, buf: &mut W) -> Result {
+    fn cwrite<W: CairoWriter>(&self, buf: &mut W) -> Result {
         match self {
             Visibility::Default => Ok(()),
             Visibility::Pub(p) => {
@@ -49,3 +49,17 @@ impl CairoWrite for Visibility {
         }
     }
 }
+
+pub fn cwrite_docs<W: CairoWriter>(docs: &[String], buf: &mut W) -> Result {
+    cwrite_comments(docs, buf, "///")
+}
+
+pub fn cwrite_comments<W: CairoWriter>(lines: &[String], buf: &mut W, prefix: &str) -> Result {
+    lines.iter().try_for_each(|line| {
+        buf.write_str(prefix)?;
+        if !line.is_empty() {
+            line.cwrite_prefixed(buf, ' ')?;
+        }
+        buf.write_char('\n')
+    })
+}
@@ -1,17 +1,30 @@
-use super::{CairoWrite, CairoWriteSlice};
+use super::{CairoWrite, CairoWriteSlice, CairoWriter};
 use crate::expr::{
     BinaryExpr, BinaryOp, Closure, ElseIfBlock, Expr, ExprPath, FixedSizeArray, ForExpr,
     FunctionCall, GenericArg, GenericArgNamed, IfExpr, IndexExpr, InlineMacroExpr, LoopExpr,
     MatchArm, MatchExpr, PathSegment, PathSegmentWithGenerics, StructArg, StructArgSingle,
     StructConstructorCall, UnaryExpr, UnaryOp, WhileExpr,
 };
-use std::fmt::{Result, Write};
+use std::fmt::Result;
+
+impl Expr {
+    /// Writes the expression as an operator operand, wrapped in parentheses if the tree would
+    /// otherwise parse differently.
+    fn cwrite_operand<W: CairoWriter>(&self, buf: &mut W, parens: bool) -> Result {
+        if parens {
+            self.cwrite_parenthesized(buf)
+        } else {
+            self.cwrite(buf)
+        }
+    }
+}
 
 impl CairoWrite for Expr {
-    fn cwrite<W: Write>(&self, buf: &mut W) -> Result {
+    fn cwrite<W: CairoWriter>(&self, buf: &mut W) -> Result {
         match self {
             Expr::Path(e) => e.cwrite(buf),
-            Expr::Literal(e) | Expr::ShortString(e) | Expr::String(e) => e.cwrite(buf),
+            Expr::Literal(e) => e.cwrite(buf),
+            Expr::ShortString(e) | Expr::String(e) => buf.write_verbatim(e),
             Expr::False => buf.write_str("false"),
             Expr::True => buf.write_str("true"),
             Expr::Parenthesized(e) => e.cwrite_parenthesized(buf),
@@ -27,7 +40,10 @@ impl CairoWrite for Expr {
             Expr::While(e) => e.cwrite(buf),
             Expr::For(e) => e.cwrite(buf),
             Expr::Closure(e) => e.cwrite(buf),
-            Expr::ErrorPropagate(e) => e.cwrite_suffixed(buf, '?'),
+            Expr::ErrorPropagate(e) => {
+                e.cwrite_operand(buf, e.needs_parens_as_postfix_operand())?;
+                buf.write_char('?')
+            }
             Expr::FieldInitShorthand(e) => e.cwrite(buf),
             Expr::Indexed(e) => e.cwrite(buf),
             Expr::InlineMacro(e) => e.cwrite(buf),
@@ -39,7 +55,7 @@ impl CairoWrite for Expr {
 }
 
 impl CairoWrite for ExprPath {
-    fn cwrite<W: Write>(&self, buf: &mut W) -> Result {
+    fn cwrite<W: CairoWriter>(&self, buf: &mut W) -> Result {
         if self.dollar {
             buf.write_char('$')?;
         }
@@ -48,7 +64,7 @@ impl CairoWrite for ExprPath {
 }
 
 impl CairoWrite for PathSegment {
-    fn cwrite<W: Write>(&self, buf: &mut W) -> Result {
+    fn cwrite<W: CairoWriter>(&self, buf: &mut W) -> Result {
         match self {
             PathSegment::Simple(s) => s.cwrite(buf),
             PathSegment::WithGenerics(s) => s.cwrite(buf),
@@ -58,7 +74,7 @@ impl CairoWrite for PathSegment {
 }
 
 impl CairoWrite for PathSegmentWithGenerics {
-    fn cwrite<W: Write>(&self, buf: &mut W) -> Result {
+    fn cwrite<W: CairoWriter>(&self, buf: &mut W) -> Result {
         self.ident.cwrite(buf)?;
         if self.separator {
             buf.write_str("::")?;
@@ -68,7 +84,7 @@ impl CairoWrite for PathSegmentWithGenerics {
 }
 
 impl CairoWrite for GenericArg {
-    fn cwrite<W: Write>(&self, buf: &mut W) -> Result {
+    fn cwrite<W: CairoWriter>(&self, buf: &mut W) -> Result {
         match self {
             GenericArg::Unnamed(ty) => ty.cwrite(buf),
             GenericArg::Named(expr) => expr.cwrite(buf),
@@ -77,7 +93,7 @@ impl CairoWrite for GenericArg {
 }
 
 impl CairoWrite for GenericArgNamed {
-    fn cwrite<W: Write>(&self, buf: &mut W) -> Result {
+    fn cwrite<W: CairoWriter>(&self, buf: &mut W) -> Result {
         self.name.cwrite(buf)?;
         buf.write_str(": ")?;
         self.value.cwrite(buf)
@@ -85,7 +101,7 @@ impl CairoWrite for GenericArgNamed {
 }
 
 impl CairoWrite for BinaryOp {
-    fn cwrite<W: Write>(&self, buf: &mut W) -> Result {
+    fn cwrite<W: CairoWriter>(&self, buf: &mut W) -> Result {
         match self {
             BinaryOp::Dot => buf.write_char('.'),
             BinaryOp::DotDot => buf.write_str(".."),
@@ -118,15 +134,17 @@ impl CairoWrite for BinaryOp {
 }
 
 impl CairoWrite for BinaryExpr {
-    fn cwrite<W: Write>(&self, buf: &mut W) -> Result {
-        self.lhs.cwrite(buf)?;
+    fn cwrite<W: CairoWriter>(&self, buf: &mut W) -> Result {
+        self.lhs
+            .cwrite_operand(buf, self.lhs.needs_parens_as_lhs(&self.op))?;
         self.op.cwrite(buf)?;
-        self.rhs.cwrite(buf)
+        self.rhs
+            .cwrite_operand(buf, self.rhs.needs_parens_as_rhs(&self.op))
     }
 }
 
 impl CairoWrite for UnaryOp {
-    fn cwrite<W: Write>(&self, buf: &mut W) -> Result {
+    fn cwrite<W: CairoWriter>(&self, buf: &mut W) -> Result {
         let val = match self {
             UnaryOp::Not => '!',
             UnaryOp::BitNot => '~',
@@ -140,28 +158,35 @@ impl CairoWrite for UnaryOp {
 }
 
 impl CairoWrite for UnaryExpr {
-    fn cwrite<W: Write>(&self, buf: &mut W) -> Result {
+    fn cwrite<W: CairoWriter>(&self, buf: &mut W) -> Result {
         self.op.cwrite(buf)?;
-        self.expr.cwrite(buf)
+        // `&&x` would lex as the `&&` operator.
+        if let (UnaryOp::Reference, Expr::Unary(inner)) = (&self.op, self.expr.as_ref())
+            && inner.op == UnaryOp::Reference
+        {
+            buf.write_char(' ')?;
+        }
+        self.expr
+            .cwrite_operand(buf, self.expr.needs_parens_as_unary_operand())
     }
 }
 
 impl CairoWrite for FunctionCall {
-    fn cwrite<W: Write>(&self, buf: &mut W) -> Result {
+    fn cwrite<W: CairoWriter>(&self, buf: &mut W) -> Result {
         self.path.cwrite(buf)?;
         self.args.cwrite_csv_parenthesized(buf)
     }
 }
 
 impl CairoWrite for StructConstructorCall {
-    fn cwrite<W: Write>(&self, buf: &mut W) -> Result {
-        self.path.cwrite(buf)?;
+    fn cwrite<W: CairoWriter>(&self, buf: &mut W) -> Result {
+        self.path.cwrite_suffixed(buf, ' ')?;
         self.args.cwrite_csv_braced(buf)
     }
 }
 
 impl CairoWrite for StructArg {
-    fn cwrite<W: Write>(&self, buf: &mut W) -> Result {
+    fn cwrite<W: CairoWriter>(&self, buf: &mut W) -> Result {
         match self {
             StructArg::Single(arg) => arg.cwrite(buf),
             StructArg::Tail(expr) => expr.cwrite_prefixed_str(buf, ".."),
@@ -170,7 +195,7 @@ impl CairoWrite for StructArg {
 }
 
 impl CairoWrite for StructArgSingle {
-    fn cwrite<W: Write>(&self, buf: &mut W) -> Result {
+    fn cwrite<W: CairoWriter>(&self, buf: &mut W) -> Result {
         self.identifier.cwrite(buf)?;
         if let Some(expr) = &self.arg_expr {
             expr.cwrite_prefixed_str(buf, ": ")?;
@@ -180,15 +205,15 @@ impl CairoWrite for StructArgSingle {
 }
 
 impl CairoWrite for MatchExpr {
-    fn cwrite<W: Write>(&self, buf: &mut W) -> Result {
+    fn cwrite<W: CairoWriter>(&self, buf: &mut W) -> Result {
         buf.write_str("match ")?;
-        self.expr.cwrite(buf)?;
+        self.expr.cwrite_suffixed(buf, ' ')?;
         self.arms.cwrite_fields_braced(buf)
     }
 }
 
 impl CairoWrite for MatchArm {
-    fn cwrite<W: Write>(&self, buf: &mut W) -> Result {
+    fn cwrite<W: CairoWriter>(&self, buf: &mut W) -> Result {
         self.patterns.cwrite(buf)?;
         buf.write_str(" => ")?;
         self.expr.cwrite(buf)
@@ -196,10 +221,10 @@ impl CairoWrite for MatchArm {
 }
 
 impl CairoWrite for IfExpr {
-    fn cwrite<W: Write>(&self, buf: &mut W) -> Result {
+    fn cwrite<W: CairoWriter>(&self, buf: &mut W) -> Result {
         buf.write_str("if ")?;
         self.conditions.cwrite(buf)?;
-        self.if_block.cwrite(buf)?;
+        self.if_block.cwrite_prefixed(buf, ' ')?;
         self.else_if_clauses.cwrite_concatenated(buf)?;
         if let Some(else_block) = &self.else_clause {
             buf.write_str(" else ")?;
@@ -210,41 +235,41 @@ impl CairoWrite for IfExpr {
 }
 
 impl CairoWrite for ElseIfBlock {
-    fn cwrite<W: Write>(&self, buf: &mut W) -> Result {
+    fn cwrite<W: CairoWriter>(&self, buf: &mut W) -> Result {
         buf.write_str(" else ")?;
         buf.write_str("if ")?;
         self.conditions.cwrite(buf)?;
-        self.body.cwrite(buf)
+        self.body.cwrite_prefixed(buf, ' ')
     }
 }
 
 impl CairoWrite for LoopExpr {
-    fn cwrite<W: Write>(&self, buf: &mut W) -> Result {
+    fn cwrite<W: CairoWriter>(&self, buf: &mut W) -> Result {
         buf.write_str("loop ")?;
         self.body.cwrite(buf)
     }
 }
 
 impl CairoWrite for WhileExpr {
-    fn cwrite<W: Write>(&self, buf: &mut W) -> Result {
+    fn cwrite<W: CairoWriter>(&self, buf: &mut W) -> Result {
         buf.write_str("while ")?;
         self.conditions.cwrite(buf)?;
-        self.body.cwrite(buf)
+        self.body.cwrite_prefixed(buf, ' ')
     }
 }
 
 impl CairoWrite for ForExpr {
-    fn cwrite<W: Write>(&self, buf: &mut W) -> Result {
+    fn cwrite<W: CairoWriter>(&self, buf: &mut W) -> Result {
         buf.write_str("for ")?;
         self.pattern.cwrite_suffixed(buf, ' ')?;
         self.identifier.cwrite_suffixed(buf, ' ')?;
         self.expr.cwrite(buf)?;
-        self.body.cwrite(buf)
+        self.body.cwrite_prefixed(buf, ' ')
     }
 }
 
 impl CairoWrite for Closure {
-    fn cwrite<W: Write>(&self, buf: &mut W) -> Result {
+    fn cwrite<W: CairoWriter>(&self, buf: &mut W) -> Result {
         self.params.cwrite_csv_barred(buf)?;
         if let Some(ret_ty) = &self.ret_ty {
             ret_ty.cwrite_prefixed_str(buf, " -> ")?;
@@ -257,21 +282,22 @@ impl CairoWrite for Closure {
 }
 
 impl CairoWrite for IndexExpr {
-    fn cwrite<W: Write>(&self, buf: &mut W) -> Result {
-        self.expr.cwrite(buf)?;
+    fn cwrite<W: CairoWriter>(&self, buf: &mut W) -> Result {
+        self.expr
+            .cwrite_operand(buf, self.expr.needs_parens_as_postfix_operand())?;
         self.index_expr.cwrite_bracketed(buf)
     }
 }
 
 impl CairoWrite for InlineMacroExpr {
-    fn cwrite<W: Write>(&self, buf: &mut W) -> Result {
+    fn cwrite<W: CairoWriter>(&self, buf: &mut W) -> Result {
         self.path.cwrite_suffixed(buf, '!')?;
         self.arguments.cwrite(buf)
     }
 }
 
 impl CairoWrite for FixedSizeArray {
-    fn cwrite<W: Write>(&self, buf: &mut W) -> Result {
+    fn cwrite<W: CairoWriter>(&self, buf: &mut W) -> Result {
         buf.write_char('[')?;
         self.exprs.cwrite_csv(buf)?;
         if let Some(size) = &self.size {
@@ -2,9 +2,15 @@ use cairo_lang_macro::{ProcMacroResult, TextSpan, Token, TokenStream, TokenTree}
 use std::fmt::Result;
 
 use crate::CairoWrite;
+use crate::fmt::{FormatConfig, PrettyWriter};
 
 pub trait CairoFormat {
     fn stringify(&self) -> String;
+    /// Indented, `scarb fmt` style output. Slower than [`CairoFormat::stringify`].
+    fn pretty(&self) -> String {
+        self.pretty_with(&FormatConfig::default())
+    }
+    fn pretty_with(&self, config: &FormatConfig) -> String;
     fn cfmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result;
     fn to_token(&self) -> Token {
         Token::new(self.stringify(), TextSpan::call_site())
@@ -33,4 +39,10 @@ impl<T: CairoWrite> CairoFormat for T {
         self.cwrite(&mut s).unwrap();
         s
     }
+    fn pretty_with(&self, config: &FormatConfig) -> String {
+        let mut writer =
+            PrettyWriter::with_config(String::with_capacity(self.size_hint()), config.clone());
+        self.cwrite(&mut writer).unwrap();
+        writer.finish().unwrap()
+    }
 }
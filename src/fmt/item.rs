@@ -1,14 +1,16 @@
-use super::{CairoWrite, CairoWriteSlice};
+use super::common::{cwrite_comments, cwrite_docs};
+use super::write::Sizer;
+use super::{CairoWrite, CairoWriteSlice, CairoWriter};
 use crate::item::{
     Constant, Enum, ExternFunction, ExternType, FunctionDeclaration, FunctionSignature,
     FunctionWithBody, Impl, ImplAlias, ImplItem, InlineMacroItem, Item, Member, Module, Struct,
     Trait, TraitConstant, TraitFunction, TraitImpl, TraitItem, TraitType, TypeAlias, UseItem,
     UsePath, UsePathLeaf, UsePathSingle, Variant,
 };
-use std::fmt::{Result, Write};
+use std::fmt::Result;
 
 impl CairoWrite for Item {
-    fn cwrite<W: Write>(&self, buf: &mut W) -> Result {
+    fn cwrite<W: CairoWriter>(&self, buf: &mut W) -> Result {
         match self {
             Item::Constant(e) => e.cwrite(buf),
             Item::Module(e) => e.cwrite(buf),
@@ -23,29 +25,94 @@ impl CairoWrite for Item {
             Item::Enum(e) => e.cwrite(buf),
             Item::TypeAlias(e) => e.cwrite(buf),
             Item::InlineMacro(e) => e.cwrite(buf),
-            Item::MacroDeclaration | Item::HeaderDoc(_) | Item::Missing => Ok(()),
+            Item::MacroDeclaration(e) => e.cwrite(buf),
+            Item::HeaderDoc(lines) => cwrite_comments(lines, buf, "//!"),
+            Item::Missing => Ok(()),
         }
     }
 }
 
 impl CairoWrite for Vec<Item> {
-    fn cwrite<W: Write>(&self, buf: &mut W) -> Result {
-        self.cwrite_terminated(buf, '\n')
+    fn cwrite<W: CairoWriter>(&self, buf: &mut W) -> Result {
+        cwrite_items(self, buf)
     }
 }
 
+/// An item in a module, trait or impl body.
+trait BodyItem: CairoWrite {
+    /// Whether the item ends with a braced body, pretty output separates it from the next
+    /// item with a blank line.
+    fn has_body(&self) -> bool;
+}
+
+impl BodyItem for Item {
+    fn has_body(&self) -> bool {
+        match self {
+            Item::Module(e) => e.body.is_some(),
+            Item::Trait(e) => e.body.is_some(),
+            Item::Impl(e) => e.body.is_some(),
+            Item::FreeFunction(_) | Item::Struct(_) | Item::Enum(_) | Item::MacroDeclaration(_) => {
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+impl BodyItem for TraitItem {
+    fn has_body(&self) -> bool {
+        matches!(self, TraitItem::Function(e) if e.body.is_some())
+    }
+}
+
+impl BodyItem for ImplItem {
+    fn has_body(&self) -> bool {
+        match self {
+            ImplItem::Module(e) => e.body.is_some(),
+            ImplItem::Trait(e) => e.body.is_some(),
+            ImplItem::Function(_) | ImplItem::Struct(_) | ImplItem::Enum(_) => true,
+            _ => false,
+        }
+    }
+}
+
+/// One item per line.
+fn cwrite_items<T: BodyItem, W: CairoWriter>(items: &[T], buf: &mut W) -> Result {
+    for (i, item) in items.iter().enumerate() {
+        if i > 0 && buf.config().is_some() && items[i - 1].has_body() {
+            buf.write_char('\n')?;
+        }
+        item.cwrite_suffixed(buf, '\n')?;
+    }
+    Ok(())
+}
+
+/// ` { ... }` around indented items.
+fn cwrite_items_braced<T: BodyItem, W: CairoWriter>(items: &[T], buf: &mut W) -> Result {
+    buf.write_str(" {")?;
+    if !items.is_empty() {
+        buf.indent();
+        buf.write_char('\n')?;
+        cwrite_items(items, buf)?;
+        buf.dedent();
+    }
+    buf.write_char('}')
+}
+
 impl CairoWrite for Struct {
-    fn cwrite<W: Write>(&self, buf: &mut W) -> Result {
+    fn cwrite<W: CairoWriter>(&self, buf: &mut W) -> Result {
+        cwrite_docs(&self.docs, buf)?;
         self.attributes.cwrite(buf)?;
         self.visibility.cwrite(buf)?;
         self.name.cwrite_prefixed_str(buf, "struct ")?;
-        self.generic_params.cwrite(buf)?;
+        self.generic_params.cwrite_suffixed(buf, ' ')?;
         self.members.cwrite_fields_braced(buf)
     }
 }
 
 impl CairoWrite for Member {
-    fn cwrite<W: Write>(&self, buf: &mut W) -> Result {
+    fn cwrite<W: CairoWriter>(&self, buf: &mut W) -> Result {
+        cwrite_docs(&self.docs, buf)?;
         self.attributes.cwrite(buf)?;
         self.visibility.cwrite(buf)?;
         self.name.cwrite(buf)?;
@@ -54,17 +121,19 @@ impl CairoWrite for Member {
 }
 
 impl CairoWrite for Enum {
-    fn cwrite<W: Write>(&self, buf: &mut W) -> Result {
+    fn cwrite<W: CairoWriter>(&self, buf: &mut W) -> Result {
+        cwrite_docs(&self.docs, buf)?;
         self.attributes.cwrite(buf)?;
         self.visibility.cwrite(buf)?;
         self.name.cwrite_prefixed_str(buf, "enum ")?;
-        self.generic_params.cwrite(buf)?;
+        self.generic_params.cwrite_suffixed(buf, ' ')?;
         self.variants.cwrite_fields_braced(buf)
     }
 }
 
 impl CairoWrite for Variant {
-    fn cwrite<W: Write>(&self, buf: &mut W) -> Result {
+    fn cwrite<W: CairoWriter>(&self, buf: &mut W) -> Result {
+        cwrite_docs(&self.docs, buf)?;
         self.attributes.cwrite(buf)?;
         self.name.cwrite(buf)?;
         if let Some(ty) = &self.type_clause {
@@ -75,7 +144,8 @@ impl CairoWrite for Variant {
 }
 
 impl CairoWrite for Constant {
-    fn cwrite<W: Write>(&self, buf: &mut W) -> Result {
+    fn cwrite<W: CairoWriter>(&self, buf: &mut W) -> Result {
+        cwrite_docs(&self.docs, buf)?;
         self.attributes.cwrite(buf)?;
         self.visibility.cwrite(buf)?;
         self.name.cwrite_prefixed_str(buf, "const ")?;
@@ -86,19 +156,21 @@ impl CairoWrite for Constant {
 }
 
 impl CairoWrite for Module {
-    fn cwrite<W: Write>(&self, buf: &mut W) -> Result {
+    fn cwrite<W: CairoWriter>(&self, buf: &mut W) -> Result {
+        cwrite_docs(&self.docs, buf)?;
         self.attributes.cwrite(buf)?;
         self.visibility.cwrite(buf)?;
         self.name.cwrite_prefixed_str(buf, "mod ")?;
         match &self.body {
-            Some(items) => items.cwrite_block_braced(buf),
+            Some(items) => cwrite_items_braced(items, buf),
             None => buf.write_char(';'),
         }
     }
 }
 
 impl CairoWrite for UseItem {
-    fn cwrite<W: Write>(&self, buf: &mut W) -> Result {
+    fn cwrite<W: CairoWriter>(&self, buf: &mut W) -> Result {
+        cwrite_docs(&self.docs, buf)?;
         self.attributes.cwrite(buf)?;
         self.visibility.cwrite(buf)?;
         buf.write_str("use ")?;
@@ -110,18 +182,18 @@ impl CairoWrite for UseItem {
 }
 
 impl CairoWrite for UsePath {
-    fn cwrite<W: Write>(&self, buf: &mut W) -> Result {
+    fn cwrite<W: CairoWriter>(&self, buf: &mut W) -> Result {
         match self {
             UsePath::Leaf(leaf) => leaf.cwrite(buf),
             UsePath::Single(single) => single.cwrite(buf),
-            UsePath::Multi(multi) => multi.cwrite_csv_braced(buf),
+            UsePath::Multi(multi) => multi.cwrite_csv_group(buf, '{', '}'),
             UsePath::Star => buf.write_char('*'),
         }
     }
 }
 
 impl CairoWrite for UsePathLeaf {
-    fn cwrite<W: Write>(&self, buf: &mut W) -> Result {
+    fn cwrite<W: CairoWriter>(&self, buf: &mut W) -> Result {
         self.ident.cwrite(buf)?;
         if let Some(alias) = &self.alias {
             alias.cwrite_prefixed_str(buf, " as ")?;
@@ -131,14 +203,15 @@ impl CairoWrite for UsePathLeaf {
 }
 
 impl CairoWrite for UsePathSingle {
-    fn cwrite<W: Write>(&self, buf: &mut W) -> Result {
+    fn cwrite<W: CairoWriter>(&self, buf: &mut W) -> Result {
         self.ident.cwrite_suffixed_str(buf, "::")?;
         self.path.cwrite(buf)
     }
 }
 
 impl CairoWrite for FunctionWithBody {
-    fn cwrite<W: Write>(&self, buf: &mut W) -> Result {
+    fn cwrite<W: CairoWriter>(&self, buf: &mut W) -> Result {
+        cwrite_docs(&self.docs, buf)?;
         self.attributes.cwrite(buf)?;
         self.visibility.cwrite(buf)?;
         self.declaration.cwrite(buf)?;
@@ -147,7 +220,7 @@ impl CairoWrite for FunctionWithBody {
 }
 
 impl CairoWrite for FunctionDeclaration {
-    fn cwrite<W: Write>(&self, buf: &mut W) -> Result {
+    fn cwrite<W: CairoWriter>(&self, buf: &mut W) -> Result {
         if self.is_const {
             buf.write_str("const ")?;
         }
@@ -157,9 +230,9 @@ impl CairoWrite for FunctionDeclaration {
     }
 }
 
-impl CairoWrite for FunctionSignature {
-    fn cwrite<W: Write>(&self, buf: &mut W) -> Result {
-        self.parameters.cwrite_csv_parenthesized(buf)?;
+impl FunctionSignature {
+    /// Writes what follows the parameters: return type, implicits and `nopanic`.
+    fn cwrite_suffix<W: CairoWriter>(&self, buf: &mut W) -> Result {
         if let Some(ret_type) = &self.return_type {
             ret_type.cwrite_prefixed_str(buf, " -> ")?;
         }
@@ -174,8 +247,19 @@ impl CairoWrite for FunctionSignature {
     }
 }
 
+impl CairoWrite for FunctionSignature {
+    fn cwrite<W: CairoWriter>(&self, buf: &mut W) -> Result {
+        let mut suffix = Sizer::new();
+        self.cwrite_suffix(&mut suffix)?;
+        self.parameters
+            .cwrite_csv_group_followed(buf, '(', ')', suffix.size())?;
+        self.cwrite_suffix(buf)
+    }
+}
+
 impl CairoWrite for ExternFunction {
-    fn cwrite<W: Write>(&self, buf: &mut W) -> Result {
+    fn cwrite<W: CairoWriter>(&self, buf: &mut W) -> Result {
+        cwrite_docs(&self.docs, buf)?;
         self.attributes.cwrite(buf)?;
         self.visibility.cwrite(buf)?;
         buf.write_str("extern ")?;
@@ -184,7 +268,8 @@ impl CairoWrite for ExternFunction {
 }
 
 impl CairoWrite for ExternType {
-    fn cwrite<W: Write>(&self, buf: &mut W) -> Result {
+    fn cwrite<W: CairoWriter>(&self, buf: &mut W) -> Result {
+        cwrite_docs(&self.docs, buf)?;
         self.attributes.cwrite(buf)?;
         self.visibility.cwrite(buf)?;
         self.name.cwrite_prefixed_str(buf, "extern type ")?;
@@ -193,20 +278,21 @@ impl CairoWrite for ExternType {
 }
 
 impl CairoWrite for Trait {
-    fn cwrite<W: Write>(&self, buf: &mut W) -> Result {
+    fn cwrite<W: CairoWriter>(&self, buf: &mut W) -> Result {
+        cwrite_docs(&self.docs, buf)?;
         self.attributes.cwrite(buf)?;
         self.visibility.cwrite(buf)?;
         self.name.cwrite_prefixed_str(buf, "trait ")?;
         self.generic_params.cwrite(buf)?;
         match &self.body {
-            Some(items) => items.cwrite_block_braced(buf),
+            Some(items) => cwrite_items_braced(items, buf),
             None => buf.write_char(';'),
         }
     }
 }
 
 impl CairoWrite for TraitItem {
-    fn cwrite<W: Write>(&self, buf: &mut W) -> Result {
+    fn cwrite<W: CairoWriter>(&self, buf: &mut W) -> Result {
         match self {
             TraitItem::Function(e) => e.cwrite(buf),
             TraitItem::Type(e) => e.cwrite(buf),
@@ -218,7 +304,8 @@ impl CairoWrite for TraitItem {
 }
 
 impl CairoWrite for TraitFunction {
-    fn cwrite<W: Write>(&self, buf: &mut W) -> Result {
+    fn cwrite<W: CairoWriter>(&self, buf: &mut W) -> Result {
+        cwrite_docs(&self.docs, buf)?;
         self.attributes.cwrite(buf)?;
         self.declaration.cwrite(buf)?;
         match &self.body {
@@ -229,7 +316,8 @@ impl CairoWrite for TraitFunction {
 }
 
 impl CairoWrite for TraitType {
-    fn cwrite<W: Write>(&self, buf: &mut W) -> Result {
+    fn cwrite<W: CairoWriter>(&self, buf: &mut W) -> Result {
+        cwrite_docs(&self.docs, buf)?;
         self.attributes.cwrite(buf)?;
         self.name.cwrite_prefixed_str(buf, "type ")?;
         self.generic_params.cwrite_suffixed(buf, ';')
@@ -237,7 +325,8 @@ impl CairoWrite for TraitType {
 }
 
 impl CairoWrite for TraitConstant {
-    fn cwrite<W: Write>(&self, buf: &mut W) -> Result {
+    fn cwrite<W: CairoWriter>(&self, buf: &mut W) -> Result {
+        cwrite_docs(&self.docs, buf)?;
         self.attributes.cwrite(buf)?;
         self.name.cwrite_prefixed_str(buf, "const ")?;
         self.ty.cwrite_prefixed_str(buf, ": ")?;
@@ -246,29 +335,32 @@ impl CairoWrite for TraitConstant {
 }
 
 impl CairoWrite for TraitImpl {
-    fn cwrite<W: Write>(&self, buf: &mut W) -> Result {
+    fn cwrite<W: CairoWriter>(&self, buf: &mut W) -> Result {
+        cwrite_docs(&self.docs, buf)?;
         self.attributes.cwrite(buf)?;
         self.name.cwrite_prefixed_str(buf, "impl ")?;
-        self.trait_path.cwrite_suffixed(buf, ';')
+        self.trait_path.cwrite_prefixed_str(buf, ": ")?;
+        buf.write_char(';')
     }
 }
 
 impl CairoWrite for Impl {
-    fn cwrite<W: Write>(&self, buf: &mut W) -> Result {
+    fn cwrite<W: CairoWriter>(&self, buf: &mut W) -> Result {
+        cwrite_docs(&self.docs, buf)?;
         self.attributes.cwrite(buf)?;
         self.visibility.cwrite(buf)?;
         self.name.cwrite_prefixed_str(buf, "impl ")?;
         self.generic_params.cwrite(buf)?;
         self.trait_path.cwrite_prefixed_str(buf, " of ")?;
         match &self.body {
-            Some(items) => items.cwrite_block_braced(buf),
+            Some(items) => cwrite_items_braced(items, buf),
             None => buf.write_char(';'),
         }
     }
 }
 
 impl CairoWrite for ImplItem {
-    fn cwrite<W: Write>(&self, buf: &mut W) -> Result {
+    fn cwrite<W: CairoWriter>(&self, buf: &mut W) -> Result {
         match self {
             ImplItem::Function(e) => e.cwrite(buf),
             ImplItem::Type(e) => e.cwrite(buf),
@@ -287,7 +379,8 @@ impl CairoWrite for ImplItem {
 }
 
 impl CairoWrite for TypeAlias {
-    fn cwrite<W: Write>(&self, buf: &mut W) -> Result {
+    fn cwrite<W: CairoWriter>(&self, buf: &mut W) -> Result {
+        cwrite_docs(&self.docs, buf)?;
         self.attributes.cwrite(buf)?;
         self.visibility.cwrite(buf)?;
         self.name.cwrite_prefixed_str(buf, "type ")?;
@@ -297,7 +390,8 @@ impl CairoWrite for TypeAlias {
 }
 
 impl CairoWrite for ImplAlias {
-    fn cwrite<W: Write>(&self, buf: &mut W) -> Result {
+    fn cwrite<W: CairoWriter>(&self, buf: &mut W) -> Result {
+        cwrite_docs(&self.docs, buf)?;
         self.attributes.cwrite(buf)?;
         self.visibility.cwrite(buf)?;
         self.name.cwrite_prefixed_str(buf, "impl ")?;
@@ -307,9 +401,10 @@ impl CairoWrite for ImplAlias {
 }
 
 impl CairoWrite for InlineMacroItem {
-    fn cwrite<W: Write>(&self, buf: &mut W) -> Result {
+    fn cwrite<W: CairoWriter>(&self, buf: &mut W) -> Result {
+        cwrite_docs(&self.docs, buf)?;
         self.attributes.cwrite(buf)?;
         self.path.cwrite_suffixed(buf, '!')?;
-        self.arguments.cwrite(buf)
+        self.arguments.cwrite_suffixed(buf, ';')
     }
 }
@@ -0,0 +1,23 @@
+use super::{CairoWrite, CairoWriteSlice, CairoWriter};
+use crate::literal::{ByteArray, Literal};
+use std::fmt::Result;
+
+impl CairoWrite for Literal {
+    fn cwrite<W: CairoWriter>(&self, buf: &mut W) -> Result {
+        write!(buf, "{}", self.value)?;
+        match self.suffix {
+            Some(suffix) => write!(buf, "_{suffix}"),
+            None => Ok(()),
+        }
+    }
+}
+
+impl CairoWrite for ByteArray {
+    fn cwrite<W: CairoWriter>(&self, buf: &mut W) -> Result {
+        buf.write_str("ByteArray { data: array![")?;
+        self.data.cwrite_csv(buf)?;
+        self.pending_word
+            .cwrite_prefixed_str(buf, "], pending_word: ")?;
+        write!(buf, ", pending_word_len: {} }}", self.pending_word_len)
+    }
+}
@@ -1,31 +1,58 @@
+use super::common::cwrite_docs;
 use crate::macros::{
     MacroDeclarationItem, MacroElement, MacroParam, MacroParamKind, MacroRepetition,
     MacroRepetitionOperator, MacroRule, TokenNode, WrappedMacro,
 };
-use crate::{CairoWrite, CairoWriteSlice};
+use crate::{CairoFormat, CairoWrite, CairoWriteSlice, CairoWriter};
+
+/// Token text has no trivia, so separate tokens that would otherwise merge into one.
+fn cwrite_elements<W: CairoWriter>(
+    elements: &[MacroElement],
+    buf: &mut W,
+    prefix: char,
+    suffix: char,
+) -> std::fmt::Result {
+    buf.write_char(prefix)?;
+    let mut prev_end: Option<char> = None;
+    for element in elements {
+        let text = element.stringify();
+        if let (Some(end), Some(start)) = (prev_end, text.chars().next())
+            && is_word_boundary(end)
+            && is_word_boundary(start)
+        {
+            buf.write_char(' ')?;
+        }
+        prev_end = text.chars().last().or(prev_end);
+        buf.write_verbatim(&text)?;
+    }
+    buf.write_char(suffix)
+}
+
+fn is_word_boundary(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '_' | '\'' | '"')
+}
 impl CairoWrite for MacroDeclarationItem {
-    fn cwrite<W: std::fmt::Write>(&self, buf: &mut W) -> std::fmt::Result {
+    fn cwrite<W: CairoWriter>(&self, buf: &mut W) -> std::fmt::Result {
+        cwrite_docs(&self.docs, buf)?;
         self.attributes.cwrite(buf)?;
         self.visibility.cwrite(buf)?;
-        buf.write_str("macro ")?;
-        self.name.cwrite(buf)?;
-        buf.write_str(" {\n")?;
-        self.rules.cwrite_block(buf)?;
-        buf.write_str("\n}")
+        self.name.cwrite_prefixed_str(buf, "macro ")?;
+        buf.write_char(' ')?;
+        self.rules.cwrite_block_braced(buf)
     }
 }
 
 impl CairoWrite for MacroRule {
-    fn cwrite<W: std::fmt::Write>(&self, buf: &mut W) -> std::fmt::Result {
+    fn cwrite<W: CairoWriter>(&self, buf: &mut W) -> std::fmt::Result {
         self.lhs.cwrite(buf)?;
         buf.write_str(" => ")?;
-        self.rhs.cwrite_concatenated_wrapped(buf, '{', '}')?;
+        cwrite_elements(&self.rhs, buf, '{', '}')?;
         buf.write_char(';')
     }
 }
 
 impl CairoWrite for MacroParam {
-    fn cwrite<W: std::fmt::Write>(&self, buf: &mut W) -> std::fmt::Result {
+    fn cwrite<W: CairoWriter>(&self, buf: &mut W) -> std::fmt::Result {
         buf.write_char('$')?;
         self.name.cwrite(buf)?;
         if let Some(kind) = &self.kind {
@@ -36,7 +63,7 @@ impl CairoWrite for MacroParam {
 }
 
 impl CairoWrite for MacroParamKind {
-    fn cwrite<W: std::fmt::Write>(&self, buf: &mut W) -> std::fmt::Result {
+    fn cwrite<W: CairoWriter>(&self, buf: &mut W) -> std::fmt::Result {
         match self {
             MacroParamKind::Identifier(value) | MacroParamKind::Expr(value) => buf.write_str(value),
             MacroParamKind::Missing => Ok(()),
@@ -45,9 +72,9 @@ impl CairoWrite for MacroParamKind {
 }
 
 impl CairoWrite for MacroRepetition {
-    fn cwrite<W: std::fmt::Write>(&self, buf: &mut W) -> std::fmt::Result {
+    fn cwrite<W: CairoWriter>(&self, buf: &mut W) -> std::fmt::Result {
         buf.write_str("$")?;
-        self.elements.cwrite_concatenated_wrapped(buf, '(', ')')?;
+        cwrite_elements(&self.elements, buf, '(', ')')?;
         if self.comma {
             buf.write_char(',')?;
         }
@@ -56,7 +83,7 @@ impl CairoWrite for MacroRepetition {
 }
 
 impl CairoWrite for MacroRepetitionOperator {
-    fn cwrite<W: std::fmt::Write>(&self, buf: &mut W) -> std::fmt::Result {
+    fn cwrite<W: CairoWriter>(&self, buf: &mut W) -> std::fmt::Result {
         match self {
             MacroRepetitionOperator::ZeroOrOne => buf.write_str("?"),
             MacroRepetitionOperator::ZeroOrMore => buf.write_str("*"),
@@ -67,21 +94,17 @@ impl CairoWrite for MacroRepetitionOperator {
 }
 
 impl CairoWrite for WrappedMacro {
-    fn cwrite<W: std::fmt::Write>(&self, buf: &mut W) -> std::fmt::Result {
+    fn cwrite<W: CairoWriter>(&self, buf: &mut W) -> std::fmt::Result {
         match self {
-            WrappedMacro::Parenthesized(elements) => {
-                elements.cwrite_concatenated_wrapped(buf, '(', ')')
-            }
-            WrappedMacro::Braced(elements) => elements.cwrite_concatenated_wrapped(buf, '{', '}'),
-            WrappedMacro::Bracketed(elements) => {
-                elements.cwrite_concatenated_wrapped(buf, '[', ']')
-            }
+            WrappedMacro::Parenthesized(elements) => cwrite_elements(elements, buf, '(', ')'),
+            WrappedMacro::Braced(elements) => cwrite_elements(elements, buf, '{', '}'),
+            WrappedMacro::Bracketed(elements) => cwrite_elements(elements, buf, '[', ']'),
         }
     }
 }
 
 impl CairoWrite for MacroElement {
-    fn cwrite<W: std::fmt::Write>(&self, buf: &mut W) -> std::fmt::Result {
+    fn cwrite<W: CairoWriter>(&self, buf: &mut W) -> std::fmt::Result {
         match self {
             MacroElement::Token(t) => t.cwrite(buf),
             MacroElement::Param(p) => p.cwrite(buf),
@@ -92,19 +115,17 @@ impl CairoWrite for MacroElement {
 }
 
 impl CairoWrite for TokenNode {
-    fn cwrite<W: std::fmt::Write>(&self, buf: &mut W) -> std::fmt::Result {
+    fn cwrite<W: CairoWriter>(&self, buf: &mut W) -> std::fmt::Result {
         match self {
-            TokenNode::Identifier(s)
-            | TokenNode::LiteralNumber(s)
-            | TokenNode::ShortString(s)
-            | TokenNode::String(s) => buf.write_str(s),
+            TokenNode::Identifier(s) | TokenNode::LiteralNumber(s) => buf.write_str(s),
+            TokenNode::ShortString(s) | TokenNode::String(s) => buf.write_verbatim(s),
             TokenNode::As => buf.write_str("as"),
             TokenNode::Const => buf.write_str("const"),
             TokenNode::Else => buf.write_str("else"),
             TokenNode::Enum => buf.write_str("enum"),
             TokenNode::Extern => buf.write_str("extern"),
             TokenNode::False => buf.write_str("false"),
-            TokenNode::Function => buf.write_str("function"),
+            TokenNode::Function => buf.write_str("fn"),
             TokenNode::If => buf.write_str("if"),
             TokenNode::While => buf.write_str("while"),
             TokenNode::For => buf.write_str("for"),
@@ -114,9 +135,9 @@ impl CairoWrite for TokenNode {
             TokenNode::Let => buf.write_str("let"),
             TokenNode::Macro => buf.write_str("macro"),
             TokenNode::Match => buf.write_str("match"),
-            TokenNode::Module => buf.write_str("module"),
+            TokenNode::Module => buf.write_str("mod"),
             TokenNode::Mut => buf.write_str("mut"),
-            TokenNode::NoPanic => buf.write_str("no_panic"),
+            TokenNode::NoPanic => buf.write_str("nopanic"),
             TokenNode::Of => buf.write_str("of"),
             TokenNode::Ref => buf.write_str("ref"),
             TokenNode::Continue => buf.write_str("continue"),
@@ -137,7 +158,7 @@ impl CairoWrite for TokenNode {
             TokenNode::ColonColon => buf.write_str("::"),
             TokenNode::Comma => buf.write_char(','),
             TokenNode::Div => buf.write_char('/'),
-            TokenNode::DivEq => buf.write_char('/'),
+            TokenNode::DivEq => buf.write_str("/="),
             TokenNode::Dollar => buf.write_char('$'),
             TokenNode::Dot => buf.write_char('.'),
             TokenNode::DotDot => buf.write_str(".."),
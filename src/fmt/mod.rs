@@ -1,11 +1,16 @@
 mod attribute;
 mod common;
 mod expr;
+#[allow(clippy::module_inception)]
 pub mod fmt;
 mod generic_param;
 mod item;
+mod literal;
 mod macros;
+pub mod pretty;
 mod statement;
+mod ty;
 pub use fmt::CairoFormat;
+pub use pretty::{FormatConfig, PrettyWriter};
 pub mod write;
-pub use write::{CairoWrite, CairoWriteSlice};
+pub use write::{CairoWrite, CairoWriteSlice, CairoWriter};
@@ -0,0 +1,144 @@
+use std::fmt::{Result, Write};
+
+use super::CairoWriter;
+
+/// Layout options for [`PrettyWriter`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FormatConfig {
+    /// Spaces per nesting level.
+    pub indent_width: usize,
+    /// Argument lists, struct literals and other comma separated groups that would take the
+    /// line past this width are written one element per line, `None` never wraps.
+    pub max_width: Option<usize>,
+    /// Add a trailing comma after the last element of multi-line groups, struct members and
+    /// enum variants.
+    pub trailing_commas: bool,
+}
+
+impl Default for FormatConfig {
+    fn default() -> Self {
+        Self {
+            indent_width: 4,
+            max_width: Some(100),
+            trailing_commas: true,
+        }
+    }
+}
+
+impl FormatConfig {
+    /// Indented output without line wrapping.
+    pub fn compact() -> Self {
+        Self {
+            max_width: None,
+            ..Self::default()
+        }
+    }
+
+    pub fn with_indent_width(mut self, indent_width: usize) -> Self {
+        self.indent_width = indent_width;
+        self
+    }
+
+    pub fn with_max_width(mut self, max_width: Option<usize>) -> Self {
+        self.max_width = max_width;
+        self
+    }
+
+    pub fn with_trailing_commas(mut self, trailing_commas: bool) -> Self {
+        self.trailing_commas = trailing_commas;
+        self
+    }
+}
+
+/// A [`CairoWriter`] that indents the blocks opened by `CairoWrite` implementations through
+/// [`CairoWriter::indent`]. Nodes read its [`FormatConfig`] to decide where lines break.
+pub struct PrettyWriter<W: Write> {
+    inner: W,
+    config: FormatConfig,
+    /// Number of open indented blocks.
+    depth: usize,
+    /// Width of the current line, `None` until something other than its indentation is written.
+    line_width: Option<usize>,
+}
+
+impl<W: Write> PrettyWriter<W> {
+    pub fn new(inner: W) -> Self {
+        Self::with_config(inner, FormatConfig::default())
+    }
+
+    pub fn with_config(inner: W, config: FormatConfig) -> Self {
+        Self {
+            inner,
+            config,
+            depth: 0,
+            line_width: None,
+        }
+    }
+
+    pub fn finish(self) -> std::result::Result<W, std::fmt::Error> {
+        Ok(self.inner)
+    }
+
+    fn indentation(&self) -> usize {
+        self.depth * self.config.indent_width
+    }
+
+    /// Writes `text`, which holds no line break, indenting it if it starts the line.
+    fn write_line_part(&mut self, text: &str) -> Result {
+        if text.is_empty() {
+            return Ok(());
+        }
+        let width = match self.line_width {
+            Some(width) => width,
+            None => {
+                let indentation = self.indentation();
+                (0..indentation).try_for_each(|_| self.inner.write_char(' '))?;
+                indentation
+            }
+        };
+        self.line_width = Some(width + text.chars().count());
+        self.inner.write_str(text)
+    }
+}
+
+impl<W: Write> CairoWriter for PrettyWriter<W> {
+    fn config(&self) -> Option<&FormatConfig> {
+        Some(&self.config)
+    }
+
+    fn column(&self) -> usize {
+        self.line_width.unwrap_or_else(|| self.indentation())
+    }
+
+    fn indent(&mut self) {
+        self.depth += 1;
+    }
+
+    fn dedent(&mut self) {
+        self.depth = self.depth.saturating_sub(1);
+    }
+
+    fn write_verbatim(&mut self, s: &str) -> Result {
+        let (first, rest) = s.split_once('\n').unwrap_or((s, ""));
+        self.write_line_part(first)?;
+        if let Some(last) = s.rsplit_once('\n').map(|(_, last)| last) {
+            self.inner.write_char('\n')?;
+            self.inner.write_str(rest)?;
+            self.line_width = Some(last.chars().count());
+        }
+        Ok(())
+    }
+}
+
+impl<W: Write> Write for PrettyWriter<W> {
+    fn write_str(&mut self, s: &str) -> Result {
+        for (i, line) in s.split('\n').enumerate() {
+            if i > 0 {
+                self.inner.write_char('\n')?;
+                self.line_width = None;
+            }
+            self.write_line_part(line)?;
+        }
+        Ok(())
+    }
+}
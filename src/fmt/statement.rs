@@ -1,13 +1,13 @@
-use super::{CairoWrite, CairoWriteSlice};
+use super::{CairoWrite, CairoWriteSlice, CairoWriter};
 use crate::statement::{
     BreakStatement, Condition, ContinueStatement, ExprStatement, LetCondition, LetStatement,
     ParamWithPatten, Pattern, PatternEnum, PatternStruct, PatternStructParam, ReturnStatement,
     Statement,
 };
-use std::fmt::{Result, Write};
+use std::fmt::Result;
 
 impl CairoWrite for Statement {
-    fn cwrite<W: Write>(&self, buf: &mut W) -> Result {
+    fn cwrite<W: CairoWriter>(&self, buf: &mut W) -> Result {
         match self {
             Statement::Let(stmt) => stmt.cwrite(buf),
             Statement::Expr(stmt) => stmt.cwrite(buf),
@@ -21,16 +21,17 @@ impl CairoWrite for Statement {
 }
 
 impl CairoWrite for Vec<Statement> {
-    fn cwrite<W: Write>(&self, buf: &mut W) -> Result {
+    fn cwrite<W: CairoWriter>(&self, buf: &mut W) -> Result {
         self.cwrite_block_braced(buf)
     }
 }
 
 impl CairoWrite for Pattern {
-    fn cwrite<W: Write>(&self, buf: &mut W) -> Result {
+    fn cwrite<W: CairoWriter>(&self, buf: &mut W) -> Result {
         match self {
             Pattern::Underscore => buf.write_char('_'),
-            Pattern::Literal(s) | Pattern::ShortString(s) | Pattern::String(s) => s.cwrite(buf),
+            Pattern::Literal(s) => s.cwrite(buf),
+            Pattern::ShortString(s) | Pattern::String(s) => buf.write_verbatim(s),
             Pattern::False => buf.write_str("false"),
             Pattern::True => buf.write_str("true"),
             Pattern::Identifier(ident) => ident.cwrite(buf),
@@ -44,13 +45,13 @@ impl CairoWrite for Pattern {
 }
 
 impl CairoWrite for Vec<Pattern> {
-    fn cwrite<W: Write>(&self, buf: &mut W) -> Result {
+    fn cwrite<W: CairoWriter>(&self, buf: &mut W) -> Result {
         self.cwrite_join(buf, " | ")
     }
 }
 
 impl CairoWrite for Condition {
-    fn cwrite<W: Write>(&self, buf: &mut W) -> Result {
+    fn cwrite<W: CairoWriter>(&self, buf: &mut W) -> Result {
         match self {
             Condition::Let(let_cond) => let_cond.cwrite(buf),
             Condition::Expr(expr) => expr.cwrite(buf),
@@ -59,13 +60,13 @@ impl CairoWrite for Condition {
 }
 
 impl CairoWrite for Vec<Condition> {
-    fn cwrite<W: Write>(&self, buf: &mut W) -> Result {
+    fn cwrite<W: CairoWriter>(&self, buf: &mut W) -> Result {
         self.cwrite_join(buf, " && ")
     }
 }
 
 impl CairoWrite for LetStatement {
-    fn cwrite<W: Write>(&self, buf: &mut W) -> Result {
+    fn cwrite<W: CairoWriter>(&self, buf: &mut W) -> Result {
         self.attributes.cwrite(buf)?;
         buf.write_str("let ")?;
         self.pattern.cwrite(buf)?;
@@ -83,7 +84,7 @@ impl CairoWrite for LetStatement {
 }
 
 impl CairoWrite for ExprStatement {
-    fn cwrite<W: Write>(&self, buf: &mut W) -> Result {
+    fn cwrite<W: CairoWriter>(&self, buf: &mut W) -> Result {
         self.attributes.cwrite(buf)?;
         self.expr.cwrite(buf)?;
         if self.semicolon {
@@ -94,7 +95,7 @@ impl CairoWrite for ExprStatement {
 }
 
 impl CairoWrite for ContinueStatement {
-    fn cwrite<W: Write>(&self, buf: &mut W) -> Result {
+    fn cwrite<W: CairoWriter>(&self, buf: &mut W) -> Result {
         self.attributes.cwrite(buf)?;
         buf.write_str("continue")?;
         buf.write_char(';')
@@ -102,7 +103,7 @@ impl CairoWrite for ContinueStatement {
 }
 
 impl CairoWrite for ReturnStatement {
-    fn cwrite<W: Write>(&self, buf: &mut W) -> Result {
+    fn cwrite<W: CairoWriter>(&self, buf: &mut W) -> Result {
         self.attributes.cwrite(buf)?;
         buf.write_str("return")?;
         if let Some(expr) = &self.expr {
@@ -113,7 +114,7 @@ impl CairoWrite for ReturnStatement {
 }
 
 impl CairoWrite for BreakStatement {
-    fn cwrite<W: Write>(&self, buf: &mut W) -> Result {
+    fn cwrite<W: CairoWriter>(&self, buf: &mut W) -> Result {
         self.attributes.cwrite(buf)?;
         buf.write_str("break")?;
         if let Some(expr) = &self.expr {
@@ -124,14 +125,14 @@ impl CairoWrite for BreakStatement {
 }
 
 impl CairoWrite for PatternStruct {
-    fn cwrite<W: Write>(&self, buf: &mut W) -> Result {
-        self.path.cwrite(buf)?;
+    fn cwrite<W: CairoWriter>(&self, buf: &mut W) -> Result {
+        self.path.cwrite_suffixed(buf, ' ')?;
         self.params.cwrite_csv_braced(buf)
     }
 }
 
 impl CairoWrite for PatternStructParam {
-    fn cwrite<W: Write>(&self, buf: &mut W) -> Result {
+    fn cwrite<W: CairoWriter>(&self, buf: &mut W) -> Result {
         match self {
             PatternStructParam::Single(param) => param.cwrite(buf),
             PatternStructParam::WithExpr(param) => param.cwrite(buf),
@@ -141,7 +142,7 @@ impl CairoWrite for PatternStructParam {
 }
 
 impl CairoWrite for ParamWithPatten {
-    fn cwrite<W: Write>(&self, buf: &mut W) -> Result {
+    fn cwrite<W: CairoWriter>(&self, buf: &mut W) -> Result {
         self.modifiers.cwrite(buf)?;
         self.name.cwrite(buf)?;
         self.pattern.cwrite_prefixed_str(buf, ": ")
@@ -149,7 +150,7 @@ impl CairoWrite for ParamWithPatten {
 }
 
 impl CairoWrite for PatternEnum {
-    fn cwrite<W: Write>(&self, buf: &mut W) -> Result {
+    fn cwrite<W: CairoWriter>(&self, buf: &mut W) -> Result {
         self.path.cwrite(buf)?;
         if let Some(pattern) = &self.pattern {
             pattern.cwrite_parenthesized(buf)?;
@@ -159,7 +160,7 @@ impl CairoWrite for PatternEnum {
 }
 
 impl CairoWrite for LetCondition {
-    fn cwrite<W: Write>(&self, buf: &mut W) -> Result {
+    fn cwrite<W: CairoWriter>(&self, buf: &mut W) -> Result {
         buf.write_str("let ")?;
         self.patterns.cwrite(buf)?;
         buf.write_str(" = ")?;
@@ -0,0 +1,36 @@
+use super::{CairoWrite, CairoWriteSlice, CairoWriter};
+use crate::Type;
+use crate::ty::{FixedSizeArrayType, TupleType};
+use std::fmt::Result;
+
+impl CairoWrite for Type {
+    fn cwrite<W: CairoWriter>(&self, buf: &mut W) -> Result {
+        match self {
+            Type::Path(path) => path.cwrite(buf),
+            Type::Tuple(tuple) => tuple.cwrite(buf),
+            Type::Unit => buf.write_str("()"),
+            Type::Snapshot(ty) => ty.cwrite_prefixed(buf, '@'),
+            Type::Reference(ty) => ty.cwrite_prefixed(buf, '&'),
+            Type::FixedSizeArray(array) => array.cwrite(buf),
+            Type::Infer => buf.write_char('_'),
+            Type::Never => buf.write_str("never"),
+            Type::Expr(expr) => expr.cwrite(buf),
+            Type::Missing => Ok(()),
+        }
+    }
+}
+
+impl CairoWrite for TupleType {
+    fn cwrite<W: CairoWriter>(&self, buf: &mut W) -> Result {
+        self.types.cwrite_tuple(buf)
+    }
+}
+
+impl CairoWrite for FixedSizeArrayType {
+    fn cwrite<W: CairoWriter>(&self, buf: &mut W) -> Result {
+        buf.write_char('[')?;
+        self.ty.cwrite(buf)?;
+        self.size.cwrite_prefixed_str(buf, "; ")?;
+        buf.write_char(']')
+    }
+}
@@ -3,6 +3,8 @@ use std::ops::Deref;
 
 use starknet_types_core::felt::Felt;
 
+use super::FormatConfig;
+
 pub trait Slice {
     type Element;
     fn elements(&self) -> &[Self::Element];
@@ -49,52 +51,143 @@ impl Default for Sizer {
     }
 }
 
+/// The output of [`CairoWrite`]. Pretty printers expose their [`FormatConfig`] so nodes can lay
+/// themselves out, plain writers produce compact single line output.
+pub trait CairoWriter: Write {
+    /// Layout options, `None` for compact output.
+    fn config(&self) -> Option<&FormatConfig> {
+        None
+    }
+
+    /// Width of the current line so far, indentation included.
+    fn column(&self) -> usize {
+        0
+    }
+
+    /// Whether `width` more characters fit on the current line.
+    fn fits(&self, width: usize) -> bool {
+        match self.config().and_then(|config| config.max_width) {
+            Some(max_width) => self.column() + width <= max_width,
+            None => true,
+        }
+    }
+
+    /// Whether the last element of a multi-line list takes a comma.
+    fn trailing_commas(&self) -> bool {
+        self.config().is_none_or(|config| config.trailing_commas)
+    }
+
+    /// Opens an indented block, lines started after this call are indented one level deeper
+    /// until the matching [`CairoWriter::dedent`].
+    fn indent(&mut self) {}
+
+    fn dedent(&mut self) {}
+
+    /// Writes text whose line breaks belong to the code, such as a multi-line string literal,
+    /// so its lines are not indented.
+    fn write_verbatim(&mut self, s: &str) -> Result {
+        self.write_str(s)
+    }
+}
+
+/// Whether `elements` fit on the current line as a comma separated list followed by `extra`
+/// characters. Measuring stops at the first element past the line, and compact writers never
+/// measure, so nested groups are not measured once per enclosing group.
+fn fits_on_line<T: CairoWrite, W: CairoWriter>(elements: &[T], buf: &W, extra: usize) -> bool {
+    let Some(max_width) = buf.config().and_then(|config| config.max_width) else {
+        return true;
+    };
+    let Some(mut available) = max_width.checked_sub(buf.column() + extra) else {
+        return false;
+    };
+    for element in elements {
+        match available.checked_sub(element.size_hint() + 2) {
+            Some(rest) => available = rest,
+            None => return false,
+        }
+    }
+    true
+}
+
+impl CairoWriter for String {}
+
+impl CairoWriter for Sizer {}
+
+impl CairoWriter for std::fmt::Formatter<'_> {}
+
+impl<W: CairoWriter + ?Sized> CairoWriter for &mut W {
+    fn config(&self) -> Option<&FormatConfig> {
+        (**self).config()
+    }
+
+    fn column(&self) -> usize {
+        (**self).column()
+    }
+
+    fn indent(&mut self) {
+        (**self).indent()
+    }
+
+    fn dedent(&mut self) {
+        (**self).dedent()
+    }
+
+    fn write_verbatim(&mut self, s: &str) -> Result {
+        (**self).write_verbatim(s)
+    }
+}
+
 pub trait CairoWrite {
-    fn cwrite<W: Write>(&self, buf: &mut W) -> Result;
+    fn cwrite<W: CairoWriter>(&self, buf: &mut W) -> Result;
     fn size_hint(&self) -> usize {
         let mut sizer = Sizer::new();
         self.cwrite(&mut sizer).unwrap();
         sizer.size()
     }
-    fn cwrite_suffixed_str<W: Write>(&self, buf: &mut W, suffix: &str) -> Result {
+    fn cwrite_suffixed_str<W: CairoWriter>(&self, buf: &mut W, suffix: &str) -> Result {
         self.cwrite(buf)?;
         buf.write_str(suffix)
     }
-    fn cwrite_prefixed<W: Write>(&self, buf: &mut W, prefix: char) -> Result {
+    fn cwrite_prefixed<W: CairoWriter>(&self, buf: &mut W, prefix: char) -> Result {
         buf.write_char(prefix)?;
         self.cwrite(buf)
     }
-    fn cwrite_prefixed_str<W: Write>(&self, buf: &mut W, prefix: &str) -> Result {
+    fn cwrite_prefixed_str<W: CairoWriter>(&self, buf: &mut W, prefix: &str) -> Result {
         buf.write_str(prefix)?;
         self.cwrite(buf)
     }
-    fn cwrite_suffixed<W: Write>(&self, buf: &mut W, suffix: char) -> Result {
+    fn cwrite_suffixed<W: CairoWriter>(&self, buf: &mut W, suffix: char) -> Result {
         self.cwrite(buf)?;
         buf.write_char(suffix)
     }
-    fn cwrite_wrapped<W: Write>(&self, buf: &mut W, prefix: char, suffix: char) -> Result {
+    fn cwrite_wrapped<W: CairoWriter>(&self, buf: &mut W, prefix: char, suffix: char) -> Result {
         buf.write_char(prefix)?;
         self.cwrite(buf)?;
         buf.write_char(suffix)
     }
-    fn cwrite_wrapped_str<W: Write>(&self, buf: &mut W, prefix: &str, suffix: &str) -> Result {
+    fn cwrite_wrapped_str<W: CairoWriter>(
+        &self,
+        buf: &mut W,
+        prefix: &str,
+        suffix: &str,
+    ) -> Result {
         buf.write_str(prefix)?;
         self.cwrite(buf)?;
         buf.write_str(suffix)
     }
-    fn cwrite_parenthesized<W: Write>(&self, buf: &mut W) -> Result {
+    fn cwrite_parenthesized<W: CairoWriter>(&self, buf: &mut W) -> Result {
         self.cwrite_wrapped(buf, '(', ')')
     }
-    fn cwrite_braced<W: Write>(&self, buf: &mut W) -> Result {
+    fn cwrite_braced<W: CairoWriter>(&self, buf: &mut W) -> Result {
         self.cwrite_wrapped(buf, '{', '}')
     }
-    fn cwrite_bracketed<W: Write>(&self, buf: &mut W) -> Result {
+    fn cwrite_bracketed<W: CairoWriter>(&self, buf: &mut W) -> Result {
         self.cwrite_wrapped(buf, '[', ']')
     }
 }
 
 impl CairoWrite for [u8; 32] {
-    fn cwrite<W: Write>(&self, buf: &mut W) -> Result {
+    fn cwrite<W: CairoWriter>(&self, buf: &mut W) -> Result {
         buf.write_str("0x")?;
         for byte in self.iter() {
             write!(buf, "{:02x}", byte)?;
@@ -104,7 +197,7 @@ impl CairoWrite for [u8; 32] {
 }
 
 impl CairoWrite for [u8; 31] {
-    fn cwrite<W: Write>(&self, buf: &mut W) -> Result {
+    fn cwrite<W: CairoWriter>(&self, buf: &mut W) -> Result {
         buf.write_str("0x00")?;
         for byte in self.iter() {
             write!(buf, "{:02x}", byte)?;
@@ -114,19 +207,19 @@ impl CairoWrite for [u8; 31] {
 }
 
 impl CairoWrite for String {
-    fn cwrite<W: Write>(&self, buf: &mut W) -> Result {
+    fn cwrite<W: CairoWriter>(&self, buf: &mut W) -> Result {
         buf.write_str(self)
     }
 }
 
 impl CairoWrite for &str {
-    fn cwrite<W: Write>(&self, buf: &mut W) -> Result {
+    fn cwrite<W: CairoWriter>(&self, buf: &mut W) -> Result {
         buf.write_str(self)
     }
 }
 
 impl CairoWrite for Felt {
-    fn cwrite<W: Write>(&self, buf: &mut W) -> Result {
+    fn cwrite<W: CairoWriter>(&self, buf: &mut W) -> Result {
         self.to_fixed_hex_string().cwrite(buf)
     }
 }
@@ -136,50 +229,43 @@ where
     Self: Slice,
     Self::Element: CairoWrite,
 {
-    fn cwrite_join<W: Write>(&self, buf: &mut W, delimiter: &str) -> Result {
+    fn cwrite_join<W: CairoWriter>(&self, buf: &mut W, delimiter: &str) -> Result {
         let elements = self.elements();
         if let Some((first, rest)) = elements.split_first() {
             first.cwrite(buf)?;
             rest.iter()
-                .map(|e| e.cwrite_prefixed_str(buf, delimiter))
-                .collect::<Result>()?;
+                .try_for_each(|e| e.cwrite_prefixed_str(buf, delimiter))?;
         }
         Ok(())
     }
-    fn cwrite_delimited<W: Write>(&self, buf: &mut W, delimiter: char) -> Result {
+    fn cwrite_delimited<W: CairoWriter>(&self, buf: &mut W, delimiter: char) -> Result {
         let elements = self.elements();
         if let Some((first, rest)) = elements.split_first() {
             first.cwrite(buf)?;
             rest.iter()
-                .map(|e| e.cwrite_prefixed(buf, delimiter))
-                .collect::<Result>()?;
+                .try_for_each(|e| e.cwrite_prefixed(buf, delimiter))?;
         }
         Ok(())
     }
-    fn cwrite_terminated<W: Write>(&self, buf: &mut W, terminator: char) -> Result {
+    fn cwrite_terminated<W: CairoWriter>(&self, buf: &mut W, terminator: char) -> Result {
         self.elements()
             .iter()
-            .map(|e| e.cwrite_suffixed(buf, terminator))
-            .collect::<Result>()?;
+            .try_for_each(|e| e.cwrite_suffixed(buf, terminator))?;
         Ok(())
     }
-    fn cwrite_terminated_str<W: Write>(&self, buf: &mut W, terminator: &str) -> Result {
+    fn cwrite_terminated_str<W: CairoWriter>(&self, buf: &mut W, terminator: &str) -> Result {
         self.elements()
             .iter()
-            .map(|e| e.cwrite_suffixed_str(buf, terminator))
-            .collect::<Result>()?;
+            .try_for_each(|e| e.cwrite_suffixed_str(buf, terminator))?;
         Ok(())
     }
 
-    fn cwrite_concatenated<W: Write>(&self, buf: &mut W) -> Result {
-        self.elements()
-            .iter()
-            .map(|e| e.cwrite(buf))
-            .collect::<Result>()?;
+    fn cwrite_concatenated<W: CairoWriter>(&self, buf: &mut W) -> Result {
+        self.elements().iter().try_for_each(|e| e.cwrite(buf))?;
         Ok(())
     }
 
-    fn cwrite_concatenated_wrapped<W: Write>(
+    fn cwrite_concatenated_wrapped<W: CairoWriter>(
         &self,
         buf: &mut W,
         prefix: char,
@@ -190,23 +276,26 @@ where
         buf.write_char(suffix)
     }
 
-    fn cwrite_csv<W: Write>(&self, buf: &mut W) -> Result {
+    fn cwrite_csv<W: CairoWriter>(&self, buf: &mut W) -> Result {
         self.cwrite_join(buf, ", ")
     }
-    fn cwrite_block<W: Write>(&self, buf: &mut W) -> Result {
+    /// One element per line in an indented block, nothing if there are no elements.
+    fn cwrite_block<W: CairoWriter>(&self, buf: &mut W) -> Result {
         let elements = self.elements();
         if !elements.is_empty() {
+            buf.indent();
             buf.write_char('\n')?;
             elements.cwrite_terminated(buf, '\n')?;
+            buf.dedent();
         }
         Ok(())
     }
-    fn cwrite_block_braced<W: Write>(&self, buf: &mut W) -> Result {
+    fn cwrite_block_braced<W: CairoWriter>(&self, buf: &mut W) -> Result {
         buf.write_char('{')?;
         self.cwrite_block(buf)?;
         buf.write_char('}')
     }
-    fn cwrite_tuple<W: Write>(&self, buf: &mut W) -> Result {
+    fn cwrite_tuple<W: CairoWriter>(&self, buf: &mut W) -> Result {
         buf.write_char('(')?;
         let elements = self.elements();
         match elements.len() {
@@ -218,51 +307,125 @@ where
         }
         buf.write_char(')')
     }
-    fn cwrite_csv_wrapped<W: Write>(&self, buf: &mut W, prefix: char, suffix: char) -> Result {
+    fn cwrite_csv_wrapped<W: CairoWriter>(
+        &self,
+        buf: &mut W,
+        prefix: char,
+        suffix: char,
+    ) -> Result {
         buf.write_char(prefix)?;
         self.cwrite_csv(buf)?;
         buf.write_char(suffix)
     }
-    fn cwrite_csv_wrapped_str<W: Write>(&self, buf: &mut W, prefix: &str, suffix: &str) -> Result {
+    fn cwrite_csv_wrapped_str<W: CairoWriter>(
+        &self,
+        buf: &mut W,
+        prefix: &str,
+        suffix: &str,
+    ) -> Result {
         buf.write_str(prefix)?;
         self.cwrite_csv(buf)?;
         buf.write_str(suffix)
     }
-    fn cwrite_csv_braced<W: Write>(&self, buf: &mut W) -> Result {
-        self.cwrite_csv_wrapped(buf, '{', '}')
+    /// Like [`CairoWriteSlice::cwrite_csv_wrapped`], but one element per line when the list
+    /// does not fit on the current line.
+    fn cwrite_csv_group<W: CairoWriter>(&self, buf: &mut W, prefix: char, suffix: char) -> Result {
+        self.cwrite_csv_group_followed(buf, prefix, suffix, 0)
     }
-    fn cwrite_csv_parenthesized<W: Write>(&self, buf: &mut W) -> Result {
-        self.cwrite_csv_wrapped(buf, '(', ')')
+    /// [`CairoWriteSlice::cwrite_csv_group`] for a list followed by `following` characters on
+    /// the same line, such as a function's return type.
+    fn cwrite_csv_group_followed<W: CairoWriter>(
+        &self,
+        buf: &mut W,
+        prefix: char,
+        suffix: char,
+        following: usize,
+    ) -> Result {
+        self.cwrite_csv_group_padded(buf, prefix, suffix, "", following)
     }
-    fn cwrite_csv_bracketed<W: Write>(&self, buf: &mut W) -> Result {
-        self.cwrite_csv_wrapped(buf, '[', ']')
+    /// [`CairoWriteSlice::cwrite_csv_group`] with `padding` inside the delimiters when the list
+    /// stays on one line, as in `Point { x: 1 }`.
+    fn cwrite_csv_group_padded<W: CairoWriter>(
+        &self,
+        buf: &mut W,
+        prefix: char,
+        suffix: char,
+        padding: &str,
+        following: usize,
+    ) -> Result {
+        let elements = self.elements();
+        if elements.is_empty() {
+            buf.write_char(prefix)?;
+            return buf.write_char(suffix);
+        }
+        if fits_on_line(elements, buf, 2 * padding.len() + following) {
+            buf.write_char(prefix)?;
+            buf.write_str(padding)?;
+            self.cwrite_csv(buf)?;
+            buf.write_str(padding)?;
+            return buf.write_char(suffix);
+        }
+        buf.write_char(prefix)?;
+        buf.indent();
+        buf.write_char('\n')?;
+        self.cwrite_lines(buf)?;
+        buf.dedent();
+        buf.write_char(suffix)
+    }
+    /// `{ a, b }`, one element per line when the list does not fit.
+    fn cwrite_csv_braced<W: CairoWriter>(&self, buf: &mut W) -> Result {
+        self.cwrite_csv_group_padded(buf, '{', '}', " ", 0)
     }
-    fn cwrite_csv_angled<W: Write>(&self, buf: &mut W) -> Result {
+    fn cwrite_csv_parenthesized<W: CairoWriter>(&self, buf: &mut W) -> Result {
+        self.cwrite_csv_group(buf, '(', ')')
+    }
+    fn cwrite_csv_bracketed<W: CairoWriter>(&self, buf: &mut W) -> Result {
+        self.cwrite_csv_group(buf, '[', ']')
+    }
+    fn cwrite_csv_angled<W: CairoWriter>(&self, buf: &mut W) -> Result {
         self.cwrite_csv_wrapped(buf, '<', '>')
     }
-    fn cwrite_csv_barred<W: Write>(&self, buf: &mut W) -> Result {
+    fn cwrite_csv_barred<W: CairoWriter>(&self, buf: &mut W) -> Result {
         self.cwrite_csv_wrapped(buf, '|', '|')
     }
-    fn cwrite_array<W: Write>(&self, buf: &mut W) -> Result {
+    fn cwrite_array<W: CairoWriter>(&self, buf: &mut W) -> Result {
         buf.write_str("array![")?;
         self.cwrite_csv(buf)?;
         buf.write_char(']')
     }
-    fn cwrite_span<W: Write>(&self, buf: &mut W) -> Result {
+    fn cwrite_span<W: CairoWriter>(&self, buf: &mut W) -> Result {
         buf.write_char('[')?;
         self.cwrite_csv(buf)?;
         buf.write_str("].span()")
     }
 
-    fn cwrite_fields<W: Write>(&self, buf: &mut W) -> Result {
+    /// One comma separated element per line, the last comma following
+    /// [`CairoWriter::trailing_commas`].
+    fn cwrite_lines<W: CairoWriter>(&self, buf: &mut W) -> Result {
+        let elements = self.elements();
+        if let Some((last, rest)) = elements.split_last() {
+            rest.cwrite_terminated_str(buf, ",\n")?;
+            last.cwrite(buf)?;
+            if buf.trailing_commas() {
+                buf.write_char(',')?;
+            }
+            buf.write_char('\n')?;
+        }
+        Ok(())
+    }
+    /// [`CairoWriteSlice::cwrite_lines`] in an indented block, nothing if there are no
+    /// elements.
+    fn cwrite_fields<W: CairoWriter>(&self, buf: &mut W) -> Result {
         let elements = self.elements();
         if !elements.is_empty() {
+            buf.indent();
             buf.write_char('\n')?;
-            elements.cwrite_terminated_str(buf, ",\n")?;
+            elements.cwrite_lines(buf)?;
+            buf.dedent();
         }
         Ok(())
     }
-    fn cwrite_fields_braced<W: Write>(&self, buf: &mut W) -> Result {
+    fn cwrite_fields_braced<W: CairoWriter>(&self, buf: &mut W) -> Result {
         buf.write_char('{')?;
         self.cwrite_fields(buf)?;
         buf.write_char('}')
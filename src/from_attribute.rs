@@ -0,0 +1,351 @@
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+use thiserror::Error;
+
+use crate::expr::{BinaryExpr, BinaryOp};
+use crate::{
+    Arg, ArgClause, Attribute, CairoFormat, Expr, ExprPath, PathSegment, Span, Spanned, Type,
+};
+
+/// Declares a struct and implements [`FromAttribute`] for it, each field being an argument of
+/// the same name.
+///
+/// Arguments are written `name: value` or `name = value`, a bare `name` sets a `bool` field.
+/// `Option` and `bool` fields may be omitted, every other field is required. A raw field name
+/// such as `r#override` reads the argument `override`, and a field marked
+/// `#[arg(rename = "name")]` reads the argument `name` instead of its own.
+///
+/// This is a declarative macro so the crate needs no proc macro dependency, `#[arg(...)]` is
+/// consumed by the macro and is not a real attribute.
+///
+/// ```ignore
+/// from_attribute! {
+///     struct KeyArgs {
+///         name: Option<String>,
+///         size: u32,
+///         packed: bool,
+///         r#override: bool,
+///         #[arg(rename = "ty")]
+///         kind: Option<Type>,
+///     }
+/// }
+///
+/// // #[key(size: 4, packed)]
+/// let args: Option<KeyArgs> = member.get_attribute_args("key").transpose()?;
+/// ```
+#[macro_export]
+macro_rules! from_attribute {
+    // Internal: `#[arg(rename = "...")]` sets the argument name of the next field.
+    (
+        @fields $header:tt $name:ident $fields:tt $args:tt [$($attrs:tt)*] [$($rename:tt)*]
+        #[arg(rename = $arg:literal)] $($rest:tt)*
+    ) => {
+        $crate::from_attribute!(@fields $header $name $fields $args [$($attrs)*] [$arg] $($rest)*);
+    };
+
+    // Internal: other field attributes are kept on the field.
+    (
+        @fields $header:tt $name:ident $fields:tt $args:tt [$($attrs:tt)*] $rename:tt
+        #[$attr:meta] $($rest:tt)*
+    ) => {
+        $crate::from_attribute!(@fields $header $name $fields $args [$($attrs)* #[$attr]] $rename $($rest)*);
+    };
+
+    // Internal: a renamed field.
+    (
+        @fields $header:tt $name:ident { $($fields:tt)* } { $($args:tt)* } [$($attrs:tt)*] [$arg:literal]
+        $field_vis:vis $field:ident: $ty:ty $(, $($rest:tt)*)?
+    ) => {
+        $crate::from_attribute!(
+            @fields $header $name
+            { $($fields)* $($attrs)* $field_vis $field: $ty, }
+            { $($args)* ($field, $arg) }
+            [] [] $($($rest)*)?
+        );
+    };
+
+    // Internal: a field read from the argument of the same name.
+    (
+        @fields $header:tt $name:ident { $($fields:tt)* } { $($args:tt)* } [$($attrs:tt)*] []
+        $field_vis:vis $field:ident: $ty:ty $(, $($rest:tt)*)?
+    ) => {
+        $crate::from_attribute!(
+            @fields $header $name
+            { $($fields)* $($attrs)* $field_vis $field: $ty, }
+            { $($args)* ($field, $crate::from_attribute::arg_name(stringify!($field))) }
+            [] [] $($($rest)*)?
+        );
+    };
+
+    // Internal: every field read.
+    (
+        @fields { $($header:tt)* } $name:ident { $($fields:tt)* } { $(($field:ident, $arg:expr))* } [] []
+    ) => {
+        $($header)* {
+            $($fields)*
+        }
+
+        impl $crate::FromAttribute for $name {
+            fn from_attribute(
+                attribute: &$crate::Attribute,
+            ) -> ::std::result::Result<Self, $crate::AttributeError> {
+                let mut args = $crate::AttributeArgs::new(attribute, &[$($arg),*])?;
+                Ok($name {
+                    $($field: args.take($arg)?),*
+                })
+            }
+        }
+    };
+
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident { $($fields:tt)* }
+    ) => {
+        $crate::from_attribute!(
+            @fields { $(#[$meta])* $vis struct $name } $name {} {} [] [] $($fields)*
+        );
+    };
+}
+
+/// The argument read into a field by [`from_attribute!`], the field name without `r#`.
+#[doc(hidden)]
+pub fn arg_name(field: &str) -> &str {
+    field.strip_prefix("r#").unwrap_or(field)
+}
+
+/// A type read from the arguments of an attribute, see [`from_attribute!`].
+pub trait FromAttribute: Sized {
+    fn from_attribute(attribute: &Attribute) -> Result<Self, AttributeError>;
+}
+
+/// The value of a single attribute argument.
+pub trait FromAttributeArg: Sized {
+    /// Reads `name: value`, or a bare `name` when `value` is `None`, failing with the reason.
+    fn from_arg(value: Option<&Expr>) -> Result<Self, String>;
+
+    /// The value of an omitted argument, `None` if the argument is required.
+    fn absent() -> Option<Self> {
+        None
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Error)]
+pub enum AttributeErrorKind {
+    #[error("unknown argument `{0}`")]
+    Unknown(String),
+    #[error("missing argument `{0}`")]
+    Missing(String),
+    #[error("duplicate argument `{0}`")]
+    Duplicate(String),
+    #[error("invalid value for `{name}`: {reason}")]
+    Invalid { name: String, reason: String },
+    #[error("expected `name: value` or a bare name, found `{0}`")]
+    Unexpected(String),
+}
+
+/// An argument of `#[attribute]` that could not be read, `span` is the argument's when known
+/// and the attribute's otherwise.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AttributeError {
+    pub attribute: String,
+    pub kind: AttributeErrorKind,
+    pub span: Option<Span>,
+}
+
+impl Display for AttributeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "#[{}]: {}", self.attribute, self.kind)
+    }
+}
+
+impl std::error::Error for AttributeError {}
+
+struct NamedValue {
+    name: String,
+    value: Option<Expr>,
+    span: Option<Span>,
+}
+
+/// The arguments of an attribute by name, used by [`from_attribute!`] and manual
+/// [`FromAttribute`] implementations.
+pub struct AttributeArgs<'a> {
+    attribute: &'a Attribute,
+    args: Vec<NamedValue>,
+}
+
+impl<'a> AttributeArgs<'a> {
+    /// Splits the arguments by name, failing on arguments outside of `known`, on repeated
+    /// ones and on positional arguments other than a bare name.
+    pub fn new(attribute: &'a Attribute, known: &[&str]) -> Result<Self, AttributeError> {
+        let mut reader = AttributeArgs {
+            attribute,
+            args: vec![],
+        };
+        for arg in attribute.arguments.iter().flatten() {
+            let span = arg.span().or(attribute.span());
+            let arg = named_value(arg).ok_or_else(|| {
+                reader.error(AttributeErrorKind::Unexpected(arg.stringify()), span)
+            })?;
+            if !known.contains(&arg.name.as_str()) {
+                return Err(reader.error(AttributeErrorKind::Unknown(arg.name), span));
+            }
+            if reader.args.iter().any(|other| other.name == arg.name) {
+                return Err(reader.error(AttributeErrorKind::Duplicate(arg.name), span));
+            }
+            reader.args.push(arg);
+        }
+        Ok(reader)
+    }
+
+    /// Removes and converts the argument `name`, falling back to [`FromAttributeArg::absent`].
+    pub fn take<T: FromAttributeArg>(&mut self, name: &str) -> Result<T, AttributeError> {
+        match self.args.iter().position(|arg| arg.name == name) {
+            Some(index) => {
+                let arg = self.args.remove(index);
+                T::from_arg(arg.value.as_ref()).map_err(|reason| {
+                    self.error(
+                        AttributeErrorKind::Invalid {
+                            name: arg.name,
+                            reason,
+                        },
+                        arg.span.or(self.attribute.span()),
+                    )
+                })
+            }
+            None => T::absent().ok_or_else(|| {
+                self.error(
+                    AttributeErrorKind::Missing(name.to_string()),
+                    self.attribute.span(),
+                )
+            }),
+        }
+    }
+
+    fn error(&self, kind: AttributeErrorKind, span: Option<Span>) -> AttributeError {
+        AttributeError {
+            attribute: self.attribute.path_string(),
+            kind,
+            span,
+        }
+    }
+}
+
+fn single_ident(expr: &Expr) -> Option<&str> {
+    match expr {
+        Expr::Path(ExprPath {
+            dollar: false,
+            path,
+            ..
+        }) => match path.as_slice() {
+            [PathSegment::Simple(name)] => Some(name),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn named_value(arg: &Arg) -> Option<NamedValue> {
+    if !arg.modifiers.is_empty() {
+        return None;
+    }
+    let (name, value) = match &arg.clause {
+        ArgClause::Named(named) => (named.name.clone(), Some(named.value.clone())),
+        ArgClause::Shorthand(name) => (
+            name.clone(),
+            Some(Expr::Path(ExprPath::new([name.as_str()]))),
+        ),
+        ArgClause::Unnamed(Expr::Binary(BinaryExpr {
+            op: BinaryOp::Eq,
+            lhs,
+            rhs,
+            ..
+        })) => (single_ident(lhs)?.to_string(), Some((**rhs).clone())),
+        ArgClause::Unnamed(expr) => (single_ident(expr)?.to_string(), None),
+    };
+    Some(NamedValue {
+        name,
+        value,
+        span: arg.span(),
+    })
+}
+
+fn required(value: Option<&Expr>) -> Result<&Expr, String> {
+    value.ok_or_else(|| "expected a value".to_string())
+}
+
+impl<T: FromAttributeArg> FromAttributeArg for Option<T> {
+    fn from_arg(value: Option<&Expr>) -> Result<Self, String> {
+        T::from_arg(value).map(Some)
+    }
+
+    fn absent() -> Option<Self> {
+        Some(None)
+    }
+}
+
+impl FromAttributeArg for bool {
+    fn from_arg(value: Option<&Expr>) -> Result<Self, String> {
+        match value {
+            None | Some(Expr::True) => Ok(true),
+            Some(Expr::False) => Ok(false),
+            Some(expr) => Err(format!(
+                "expected `true` or `false`, found `{}`",
+                expr.stringify()
+            )),
+        }
+    }
+
+    fn absent() -> Option<Self> {
+        Some(false)
+    }
+}
+
+impl FromAttributeArg for String {
+    /// A string or short string literal.
+    fn from_arg(value: Option<&Expr>) -> Result<Self, String> {
+        required(value)?
+            .to_string_value()
+            .map_err(|error| error.to_string())
+    }
+}
+
+impl FromAttributeArg for Expr {
+    fn from_arg(value: Option<&Expr>) -> Result<Self, String> {
+        required(value).cloned()
+    }
+}
+
+impl FromAttributeArg for ExprPath {
+    fn from_arg(value: Option<&Expr>) -> Result<Self, String> {
+        match required(value)? {
+            Expr::Path(path) => Ok(path.clone()),
+            expr => Err(format!("expected a path, found `{}`", expr.stringify())),
+        }
+    }
+}
+
+impl FromAttributeArg for Type {
+    fn from_arg(value: Option<&Expr>) -> Result<Self, String> {
+        Type::try_from(required(value)?.clone())
+            .map_err(|expr| format!("expected a type, found `{}`", expr.stringify()))
+    }
+}
+
+macro_rules! from_attribute_arg_int {
+    ($($ty:ty),* $(,)?) => {
+        $(impl FromAttributeArg for $ty {
+            fn from_arg(value: Option<&Expr>) -> Result<Self, String> {
+                let literal = required(value)?
+                    .to_literal()
+                    .map_err(|error| error.to_string())?;
+                <$ty>::try_from(&literal.value).map_err(|_| {
+                    format!("{} is out of range for {}", literal.value, stringify!($ty))
+                })
+            }
+        })*
+    };
+}
+
+from_attribute_arg_int!(
+    u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize
+);
@@ -1,8 +1,10 @@
-use std::fmt::{Result as FmtResult, Write};
+use std::fmt::Result as FmtResult;
 
+use crate::expr::{GenericArg, PathSegmentWithGenerics};
 use crate::{
-    CairoWriteSlice, Expr, ExprPath, from_typed_syntax_node, syntax_enum, syntax_option,
-    syntax_type, typed_syntax_node_to_string_without_trivia, vec_from_element_list,
+    CairoFormat, CairoWriteSlice, CairoWriter, Expr, ExprPath, PathSegment, Type,
+    from_typed_syntax_node, syntax_enum, syntax_option, syntax_type,
+    typed_syntax_node_to_string_without_trivia, vec_from_element_list,
 };
 
 syntax_enum! {
@@ -18,7 +20,7 @@ syntax_enum! {
 syntax_type! {
     ConstGenericParam[GenericParamConst]{
         name: String,
-        ty: Expr,
+        ty: Type,
     }
 }
 
@@ -56,8 +58,68 @@ AssociatedItemConstraint}
 vec_from_element_list! {GenericParamList, GenericParam}
 vec_from_element_list! {WrappedGenericParamList.generic_params, GenericParam}
 
+impl GenericParam {
+    /// `+trait_path<ty>`, for example `GenericParam::bound("Drop", ExprPath::new(["T"]))`.
+    /// `ty` is appended to generic arguments the trait path already has.
+    ///
+    /// # Panics
+    ///
+    /// If `trait_path` has no segments or ends with a missing one.
+    pub fn bound(trait_path: impl Into<ExprPath>, ty: impl Into<Type>) -> Self {
+        let mut trait_path = trait_path.into();
+        let arg = GenericArg::Unnamed(ty.into());
+        match trait_path.path.last_mut() {
+            Some(PathSegment::WithGenerics(segment)) => segment.args.push(arg),
+            Some(segment @ PathSegment::Simple(_)) => {
+                let ident = segment.ident().unwrap_or_default().to_string();
+                *segment = PathSegment::WithGenerics(PathSegmentWithGenerics {
+                    ident,
+                    separator: false,
+                    args: vec![arg],
+                    span: None,
+                });
+            }
+            Some(PathSegment::Missing) | None => {
+                panic!(
+                    "a bound needs a trait name, found `{}`",
+                    trait_path.stringify()
+                )
+            }
+        }
+        GenericParam::ImplAnonymous(ImplAnonymousGenericParam {
+            trait_path,
+            type_constrains: None,
+            span: None,
+        })
+    }
+
+    /// The declared name, `None` for anonymous and negative impls.
+    pub fn name(&self) -> Option<&str> {
+        match self {
+            GenericParam::Type(name) => Some(name),
+            GenericParam::Const(param) => Some(&param.name),
+            GenericParam::ImplNamed(param) => Some(&param.name),
+            GenericParam::ImplAnonymous(_) | GenericParam::NegativeImpl(_) => None,
+        }
+    }
+
+    /// The name to pass as a generic argument, as in `Foo<T, N>`. Impl parameters are
+    /// inferred at use sites so only type and const parameters have one.
+    pub fn usage_name(&self) -> Option<&str> {
+        match self {
+            GenericParam::Type(name) => Some(name),
+            GenericParam::Const(param) => Some(&param.name),
+            _ => None,
+        }
+    }
+}
+
 pub trait GenericParamsTrait {
     fn generic_params(&self) -> &Option<Vec<GenericParam>>;
+    /// Every parameter, including const and impl ones.
+    fn all_generic_params(&self) -> &[GenericParam] {
+        self.generic_params().as_deref().unwrap_or_default()
+    }
     fn generic_types(&self) -> Option<Vec<&str>> {
         if let Some(params) = self.generic_params() {
             params
@@ -72,26 +134,76 @@ pub trait GenericParamsTrait {
             None
         }
     }
+    /// The generic arguments referring back to the parameters, type and const ones in order.
+    fn generic_usage(&self) -> Option<Vec<&str>> {
+        let names: Vec<&str> = self
+            .all_generic_params()
+            .iter()
+            .filter_map(GenericParam::usage_name)
+            .collect();
+        (!names.is_empty()).then_some(names)
+    }
     fn generic_types_string(&self) -> String {
         let mut buf = String::new();
         self.cwrite_generic_types(&mut buf).unwrap();
         buf
     }
-    fn cwrite_generic_types<W: Write>(&self, buf: &mut W) -> FmtResult {
-        if let Some(generics) = self.generic_types() {
+    /// The usage form `<T, N>`, empty if there is nothing to pass.
+    fn cwrite_generic_types<W: CairoWriter>(&self, buf: &mut W) -> FmtResult {
+        if let Some(generics) = self.generic_usage() {
             generics.cwrite_csv_angled(buf)?;
         };
         Ok(())
     }
-    fn cwrite_generic_types_call<W: Write>(&self, buf: &mut W) -> FmtResult {
-        if let Some(generics) = self.generic_types() {
+    /// The usage form in expression position, `::<T, N>`.
+    fn cwrite_generic_types_call<W: CairoWriter>(&self, buf: &mut W) -> FmtResult {
+        if let Some(generics) = self.generic_usage() {
             buf.write_str("::")?;
             generics.cwrite_csv_angled(buf)?;
         };
         Ok(())
     }
+    /// The declaration form `<T, const N: usize, +Drop<T>>`, empty without parameters.
+    fn generic_declaration_string(&self) -> String {
+        let mut buf = String::new();
+        self.cwrite_generic_declaration(&mut buf).unwrap();
+        buf
+    }
+    fn cwrite_generic_declaration<W: CairoWriter>(&self, buf: &mut W) -> FmtResult {
+        match self.all_generic_params() {
+            [] => Ok(()),
+            params => params.cwrite_csv_angled(buf),
+        }
+    }
+    /// The parameters followed by `+Trait<T>` for every type parameter `T` and each of
+    /// `traits`, skipping bounds already declared.
+    fn generic_params_with_bounds(&self, traits: &[&str]) -> Option<Vec<GenericParam>> {
+        let mut params = self.all_generic_params().to_vec();
+        for ty in self.generic_types().unwrap_or_default() {
+            for trait_name in traits {
+                let bound = GenericParam::bound(*trait_name, ExprPath::new([ty]));
+                if !params.contains(&bound) {
+                    params.push(bound);
+                }
+            }
+        }
+        (!params.is_empty()).then_some(params)
+    }
+    /// The parameters followed by those of `other`, skipping parameters already declared or
+    /// whose name is already taken.
+    fn merged_generic_params(&self, other: &impl GenericParamsTrait) -> Option<Vec<GenericParam>> {
+        let mut params = self.all_generic_params().to_vec();
+        for param in other.all_generic_params() {
+            let taken = params.iter().any(|existing| {
+                existing == param || (param.name().is_some() && existing.name() == param.name())
+            });
+            if !taken {
+                params.push(param.clone());
+            }
+        }
+        (!params.is_empty()).then_some(params)
+    }
 }
-
 impl GenericParamsTrait for Option<Vec<GenericParam>> {
     fn generic_params(&self) -> &Option<Vec<GenericParam>> {
         self
@@ -1,11 +1,19 @@
+use crate::ast::checked_from_ast;
+use crate::ast::inner_doc_comments;
+use crate::diagnostic::{
+    ParseDiagnostic, ParseError, ParseResult, Severity, collect_diagnostics, strict,
+};
+use crate::macros::MacroDeclarationItem;
 use crate::{
-    AstInto, Attribute, Expr, ExprPath, FromAst, GenericParam, Param, PathSegment, Statement,
-    Visibility, syntax_enum, syntax_option, syntax_type, terminal_to_string,
-    typed_syntax_node_to_string_without_trivia, vec_from_element_list,
+    AstInto, AstResult, Attribute, Expr, ExprPath, FromAst, GenericParam, NameTrait, Param,
+    PathSegment, Span, Spanned, Statement, TryFromAst, Type, Visibility, syntax_enum,
+    syntax_option, syntax_type, typed_syntax_node_to_string_without_trivia, vec_from_element_list,
 };
 use cairo_lang_macro::TokenStream;
 use cairo_lang_parser::utils::SimpleParserDatabase;
-use cairo_lang_syntax::node::ast::{ModuleItem, SyntaxFile};
+use cairo_lang_syntax::node::TypedSyntaxNode;
+use cairo_lang_syntax::node::ast::{ItemHeaderDoc, SyntaxFile};
+use salsa::Database;
 
 syntax_enum! {
     Item[ModuleItem]{
@@ -22,14 +30,15 @@ syntax_enum! {
         Enum(Enum),
         TypeAlias(TypeAlias),
         InlineMacro(InlineMacroItem),
-        MacroDeclaration,
-        HeaderDoc(String),
+        MacroDeclaration(MacroDeclarationItem),
+        HeaderDoc(Vec<String>),
         Missing,
     }
 }
 
 syntax_type! {
     Struct[ItemStruct]{
+        docs: Vec<String>,
         attributes: Vec<Attribute>,
         visibility: Visibility,
         name: String,
@@ -40,15 +49,17 @@ syntax_type! {
 
 syntax_type! {
     Member{
+        docs: Vec<String>,
         attributes: Vec<Attribute>,
         visibility: Visibility,
         name: String,
-        ty[type_clause]: Expr,
+        ty[type_clause]: Type,
     }
 }
 
 syntax_type! {
     Enum[ItemEnum]{
+        docs: Vec<String>,
         attributes: Vec<Attribute>,
         visibility: Visibility,
         name: String,
@@ -59,23 +70,26 @@ syntax_type! {
 
 syntax_type! {
     Variant{
+        docs: Vec<String>,
         attributes: Vec<Attribute>,
         name: String,
-        type_clause: Option<Expr>,
+        type_clause: Option<Type>,
     }
 }
 
 syntax_type! {
     Constant[ItemConstant]{
+        docs: Vec<String>,
         attributes: Vec<Attribute>,
         visibility: Visibility,
         name: String,
-        ty[type_clause]: Expr,
+        ty[type_clause]: Type,
         value: Expr,
     }
 }
 
 syntax_type! { Module[ItemModule]{
+        docs: Vec<String>,
         attributes: Vec<Attribute>,
         visibility: Visibility,
         name: String,
@@ -83,6 +97,7 @@ syntax_type! { Module[ItemModule]{
 
 }}
 syntax_type! { UseItem[ItemUse]{
+    docs: Vec<String>,
     attributes: Vec<Attribute>,
         visibility: Visibility,
         dollar: bool,
@@ -90,6 +105,7 @@ syntax_type! { UseItem[ItemUse]{
 
 }}
 syntax_type! { FunctionWithBody{
+        docs: Vec<String>,
         attributes: Vec<Attribute>,
         visibility: Visibility,
         declaration: FunctionDeclaration,
@@ -104,23 +120,26 @@ syntax_type! { FunctionDeclaration{
 }}
 syntax_type! {FunctionSignature{
     parameters: Vec<Param>,
-    return_type[ret_ty]: Option<Expr>,
+    return_type[ret_ty]: Option<Type>,
     implicits_clause: Option<Vec<ExprPath>>,
     no_panic[optional_no_panic]: bool,
 }}
 
 syntax_type! { ExternFunction[ItemExternFunction]{
+        docs: Vec<String>,
         attributes: Vec<Attribute>,
         visibility: Visibility,
         declaration: FunctionDeclaration,
 }}
 syntax_type! { ExternType[ItemExternType]{
+        docs: Vec<String>,
         attributes: Vec<Attribute>,
         visibility: Visibility,
         name: String,
         generic_params: Option<Vec<GenericParam>>,
 }}
 syntax_type! { Trait[ItemTrait]{
+        docs: Vec<String>,
         attributes: Vec<Attribute>,
         visibility: Visibility,
         name: String,
@@ -128,6 +147,7 @@ syntax_type! { Trait[ItemTrait]{
         body: Option<Vec<TraitItem>>,
 }}
 syntax_type! { Impl[ItemImpl]{
+        docs: Vec<String>,
         attributes: Vec<Attribute>,
         visibility: Visibility,
         name: String,
@@ -136,6 +156,7 @@ syntax_type! { Impl[ItemImpl]{
         body: Option<Vec<ImplItem>>,
 }}
 syntax_type! { ImplAlias[ItemImplAlias]{
+        docs: Vec<String>,
         attributes: Vec<Attribute>,
         visibility: Visibility,
         name: String,
@@ -144,13 +165,15 @@ syntax_type! { ImplAlias[ItemImplAlias]{
 }}
 
 syntax_type! { TypeAlias[ItemTypeAlias]{
+        docs: Vec<String>,
         attributes: Vec<Attribute>,
         visibility: Visibility,
         name: String,
         generic_params: Option<Vec<GenericParam>>,
-        ty: Expr,
+        ty: Type,
 }}
 syntax_type! { InlineMacroItem[ItemInlineMacro]{
+        docs: Vec<String>,
         attributes: Vec<Attribute>,
         path: ExprPath,
         arguments: String,
@@ -172,6 +195,7 @@ syntax_type! {
 
 syntax_type! {
     TraitFunction[TraitItemFunction]{
+        docs: Vec<String>,
         attributes: Vec<Attribute>,
         declaration: FunctionDeclaration,
         body: Option<Vec<Statement>>,
@@ -180,14 +204,16 @@ syntax_type! {
 
 syntax_type! {
     TraitConstant[TraitItemConstant]{
+        docs: Vec<String>,
         attributes: Vec<Attribute>,
         name: String,
-        ty[type_clause]: Expr,
+        ty[type_clause]: Type,
     }
 }
 
 syntax_type! {
     TraitType[TraitItemType]{
+        docs: Vec<String>,
         attributes: Vec<Attribute>,
         name: String,
         generic_params: Option<Vec<GenericParam>>,
@@ -196,6 +222,7 @@ syntax_type! {
 
 syntax_type! {
     TraitImpl[TraitItemImpl]{
+        docs: Vec<String>,
         attributes: Vec<Attribute>,
         name: String,
         trait_path: ExprPath,
@@ -247,7 +274,6 @@ syntax_option! {MaybeImplBody{Some: Vec<ImplItem>, None}}
 
 vec_from_element_list!(UsePathMulti.use_paths, UsePath);
 typed_syntax_node_to_string_without_trivia! {AliasClause.alias}
-terminal_to_string! {ItemHeaderDoc.empty}
 
 vec_from_element_list!(ImplicitsClause.implicits, ExprPath);
 vec_from_element_list!(ModuleBody.items, Item);
@@ -257,17 +283,93 @@ vec_from_element_list!(SyntaxFile.items, Item);
 vec_from_element_list!(MemberList, Member);
 vec_from_element_list!(VariantList, Variant);
 
-pub fn items_from_token_stream(token_stream: TokenStream) -> Vec<Item> {
-    let db = SimpleParserDatabase::default();
-    let (node, _diagnostics) = db.parse_virtual_with_diagnostics(token_stream);
-    FromAst::<SyntaxFile>::from_syntax_node(&db, node)
+impl<'db> FromAst<'db, ItemHeaderDoc<'db>> for Vec<String> {
+    fn from_ast(ast: ItemHeaderDoc<'db>, db: &'db dyn Database) -> Self {
+        inner_doc_comments(ast.as_syntax_node(), db)
+    }
+}
+
+impl<'db> TryFromAst<'db, ItemHeaderDoc<'db>> for Vec<String> {
+    fn try_from_ast(ast: ItemHeaderDoc<'db>, db: &'db dyn Database) -> AstResult<Self> {
+        checked_from_ast(ast, db)
+    }
 }
 
-pub fn item_from_token_stream(token_stream: TokenStream) -> Item {
+/// Parses items, failing if the parser emitted an error. Warnings are dropped, see
+/// [`items_from_token_stream_with_diagnostics`] to keep them.
+pub fn items_from_token_stream(token_stream: TokenStream) -> ParseResult<Vec<Item>> {
+    without_errors(items_from_token_stream_with_diagnostics(token_stream))
+}
+
+/// Parses a single item, failing if the parser emitted an error or the input does not hold
+/// exactly one item. Warnings are dropped.
+pub fn item_from_token_stream(token_stream: TokenStream) -> ParseResult<Item> {
+    without_errors(item_from_token_stream_with_diagnostics(token_stream))?.ok_or(ParseError::NoItem)
+}
+
+/// Parses items, returning them along with every diagnostic the parser emitted.
+pub fn items_from_token_stream_with_diagnostics(
+    token_stream: TokenStream,
+) -> (Vec<Item>, Vec<ParseDiagnostic>) {
     let db = SimpleParserDatabase::default();
-    let (node, _diagnostics) = db.parse_virtual_with_diagnostics(token_stream);
-    let item_node = node.get_children(&db)[0].get_children(&db)[0];
-    FromAst::<ModuleItem>::from_syntax_node(&db, item_node)
+    let (node, diagnostics) = db.parse_virtual_with_diagnostics(token_stream);
+    (
+        FromAst::<SyntaxFile>::from_syntax_node(&db, node),
+        collect_diagnostics(&db, diagnostics),
+    )
+}
+
+/// Parses a single item, returning it along with every diagnostic the parser emitted. The
+/// item is `None` if the input holds no item or more than one, with an error diagnostic on the
+/// second item or at the start of an empty input.
+pub fn item_from_token_stream_with_diagnostics(
+    token_stream: TokenStream,
+) -> (Option<Item>, Vec<ParseDiagnostic>) {
+    let (mut items, mut diagnostics) = items_from_token_stream_with_diagnostics(token_stream);
+    if items.len() == 1 {
+        return (items.pop(), diagnostics);
+    }
+    let (message, span) = match items.get(1) {
+        None => ("expected an item".to_string(), None),
+        Some(second) => (
+            format!("expected a single item, found {}", items.len()),
+            second.span(),
+        ),
+    };
+    diagnostics.push(ParseDiagnostic {
+        message,
+        severity: Severity::Error,
+        span: span.unwrap_or_default(),
+    });
+    (None, diagnostics)
+}
+
+/// Returns `value` unless one of the diagnostics is an error.
+fn without_errors<T>((value, diagnostics): (T, Vec<ParseDiagnostic>)) -> ParseResult<T> {
+    let errors: Vec<ParseDiagnostic> = diagnostics
+        .into_iter()
+        .filter(ParseDiagnostic::is_error)
+        .collect();
+    if errors.is_empty() {
+        Ok(value)
+    } else {
+        Err(ParseError::Diagnostics(errors))
+    }
+}
+
+/// Strict variant of [`items_from_token_stream`], failing if any diagnostic was emitted.
+pub fn try_items_from_token_stream(token_stream: TokenStream) -> ParseResult<Vec<Item>> {
+    strict(items_from_token_stream_with_diagnostics(token_stream))
+}
+
+/// Strict variant of [`item_from_token_stream`], failing if any diagnostic was emitted.
+pub fn try_item_from_token_stream(token_stream: TokenStream) -> ParseResult<Item> {
+    let mut items = try_items_from_token_stream(token_stream)?;
+    match items.len() {
+        0 => Err(ParseError::NoItem),
+        1 => Ok(items.remove(0)),
+        _ => Err(ParseError::Expected("a single item")),
+    }
 }
 
 impl Item {
@@ -286,9 +388,99 @@ impl Item {
             Item::Enum(_) => "Enum",
             Item::TypeAlias(_) => "TypeAlias",
             Item::InlineMacro(_) => "InlineMacroItem",
-            Item::MacroDeclaration => "MacroDeclaration",
+            Item::MacroDeclaration(_) => "MacroDeclaration",
             Item::HeaderDoc(_) => "HeaderDoc",
             Item::Missing => "Missing",
         }
     }
+
+    /// The declared name, `None` for `use` items, inline macros, header docs and missing items.
+    pub fn name(&self) -> Option<&str> {
+        match self {
+            Item::Constant(e) => Some(e.name()),
+            Item::Module(e) => Some(e.name()),
+            Item::FreeFunction(e) => Some(&e.declaration.name),
+            Item::ExternFunction(e) => Some(&e.declaration.name),
+            Item::ExternType(e) => Some(e.name()),
+            Item::Trait(e) => Some(e.name()),
+            Item::Impl(e) => Some(e.name()),
+            Item::ImplAlias(e) => Some(e.name()),
+            Item::Struct(e) => Some(e.name()),
+            Item::Enum(e) => Some(e.name()),
+            Item::TypeAlias(e) => Some(e.name()),
+            Item::MacroDeclaration(e) => Some(e.name()),
+            Item::Use(_) | Item::InlineMacro(_) | Item::HeaderDoc(_) | Item::Missing => None,
+        }
+    }
+}
+
+macro_rules! item_from {
+    ($($variant:ident($node:ident)),+ $(,)?) => {
+        $(impl From<$node> for Item {
+            fn from(node: $node) -> Self {
+                Item::$variant(node)
+            }
+        })+
+    };
+}
+
+item_from! {
+    FreeFunction(FunctionWithBody),
+    Trait(Trait),
+    Impl(Impl),
+    Struct(Struct),
+    Enum(Enum),
+}
+
+impl Spanned for Item {
+    fn span(&self) -> Option<Span> {
+        match self {
+            Item::Constant(e) => e.span(),
+            Item::Module(e) => e.span(),
+            Item::Use(e) => e.span(),
+            Item::FreeFunction(e) => e.span(),
+            Item::ExternFunction(e) => e.span(),
+            Item::ExternType(e) => e.span(),
+            Item::Trait(e) => e.span(),
+            Item::Impl(e) => e.span(),
+            Item::ImplAlias(e) => e.span(),
+            Item::Struct(e) => e.span(),
+            Item::Enum(e) => e.span(),
+            Item::TypeAlias(e) => e.span(),
+            Item::InlineMacro(e) => e.span(),
+            Item::MacroDeclaration(e) => e.span(),
+            Item::HeaderDoc(_) | Item::Missing => None,
+        }
+    }
+}
+
+impl Spanned for TraitItem {
+    fn span(&self) -> Option<Span> {
+        match self {
+            TraitItem::Function(e) => e.span(),
+            TraitItem::Type(e) => e.span(),
+            TraitItem::Constant(e) => e.span(),
+            TraitItem::Impl(e) => e.span(),
+            TraitItem::Missing => None,
+        }
+    }
+}
+
+impl Spanned for ImplItem {
+    fn span(&self) -> Option<Span> {
+        match self {
+            ImplItem::Function(e) => e.span(),
+            ImplItem::Type(e) => e.span(),
+            ImplItem::Constant(e) => e.span(),
+            ImplItem::Impl(e) => e.span(),
+            ImplItem::Module(e) => e.span(),
+            ImplItem::Use(e) => e.span(),
+            ImplItem::ExternFunction(e) => e.span(),
+            ImplItem::ExternType(e) => e.span(),
+            ImplItem::Trait(e) => e.span(),
+            ImplItem::Struct(e) => e.span(),
+            ImplItem::Enum(e) => e.span(),
+            ImplItem::Missing => None,
+        }
+    }
 }
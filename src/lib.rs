@@ -1,21 +1,67 @@
+//! Owned, printable AST for Cairo source, built on top of `cairo-lang-syntax`.
+//!
+//! # Serde
+//!
+//! With the `serde` feature every AST node, [`Span`] and [`ParseDiagnostic`] implements
+//! `Serialize` and `Deserialize`. The JSON shape mirrors the Rust definitions:
+//!
+//! - structs are objects keyed by field name, `span` is `{"start": .., "end": ..}` and is
+//!   omitted when `None`;
+//! - enum variants are externally tagged, `{"Path": {..}}`, and unit variants are plain
+//!   strings such as `"Missing"` or `"Default"`;
+//! - `Box` is transparent and `Option` is `null` when absent.
+//!
+//! Renaming or removing a field or variant changes the shape and is treated as a breaking
+//! change.
+
 pub mod ast;
 pub mod attribute;
+pub mod builder;
 pub mod common;
 pub mod crate_macros;
+pub mod diagnostic;
+pub mod diff;
 pub mod expr;
 pub mod fmt;
+pub mod from_attribute;
 pub mod generic_param;
 pub mod item;
+pub mod literal;
 pub mod macros;
+pub mod parse;
+pub mod quote;
 pub mod reader;
+pub mod span;
 pub mod statement;
+pub mod ty;
 pub mod utils;
-pub use ast::{AstInto, AstToString, FromAst};
+pub mod visit;
+pub use ast::{AstError, AstInto, AstResult, AstToString, AstTryInto, FromAst, TryFromAst};
 pub use attribute::{Arg, ArgClause, Attribute, AttributesTrait, NamedArg};
-pub use common::{Identifier, Modifier, NameTrait, Param, Visibility, VisibilityTrait};
+pub use builder::{EnumBuilder, FunctionBuilder, ImplBuilder, StructBuilder};
+pub use common::{DocsTrait, Identifier, Modifier, NameTrait, Param, Visibility, VisibilityTrait};
+pub use diagnostic::{ParseDiagnostic, ParseError, ParseResult, Severity};
+pub use diff::{AstDiff, Diffable, Edit, diff};
 pub use expr::{Expr, ExprPath, FixedSizeArray, PathSegment};
-pub use fmt::{CairoFormat, CairoWrite, CairoWriteSlice};
+pub use fmt::{CairoFormat, CairoWrite, CairoWriteSlice, CairoWriter, FormatConfig, PrettyWriter};
+pub use from_attribute::{
+    AttributeArgs, AttributeError, AttributeErrorKind, FromAttribute, FromAttributeArg,
+};
 pub use generic_param::{GenericParam, GenericParamsTrait};
-pub use item::{Enum, Item, Member, Struct, Variant, items_from_token_stream};
+pub use item::{
+    Enum, Item, Member, Struct, Variant, item_from_token_stream,
+    item_from_token_stream_with_diagnostics, items_from_token_stream,
+    items_from_token_stream_with_diagnostics, try_item_from_token_stream,
+    try_items_from_token_stream,
+};
+pub use literal::{ByteArray, IntType, Literal, LiteralError};
+pub use parse::{
+    Parse, parse_attribute, parse_expr, parse_generic_param, parse_path, parse_pattern,
+    parse_statement, parse_statements, parse_type,
+};
+pub use quote::Quoted;
+pub use span::{Span, Spanned};
 pub use statement::{Condition, Pattern, Statement};
+pub use ty::Type;
 pub use utils::{Slice, str_to_token_stream};
+pub use visit::{Fold, Visit, VisitMut, Visitable};
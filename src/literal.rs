@@ -0,0 +1,480 @@
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::str::FromStr;
+
+use num_bigint::{BigInt, Sign};
+use num_traits::{Num, One, Signed, ToPrimitive};
+use starknet_types_core::felt::Felt;
+use thiserror::Error;
+
+use crate::expr::{UnaryExpr, UnaryOp};
+use crate::{CairoFormat, Expr, Pattern};
+
+/// The integer types a numeric literal can be suffixed with, as in `1_u8`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum IntType {
+    U8,
+    U16,
+    U32,
+    U64,
+    U128,
+    U256,
+    Usize,
+    I8,
+    I16,
+    I32,
+    I64,
+    I128,
+    Felt252,
+}
+
+const INT_TYPES: [IntType; 13] = [
+    IntType::U8,
+    IntType::U16,
+    IntType::U32,
+    IntType::U64,
+    IntType::U128,
+    IntType::U256,
+    IntType::Usize,
+    IntType::I8,
+    IntType::I16,
+    IntType::I32,
+    IntType::I64,
+    IntType::I128,
+    IntType::Felt252,
+];
+
+impl IntType {
+    pub fn from_suffix(suffix: &str) -> Option<Self> {
+        INT_TYPES.into_iter().find(|ty| ty.as_str() == suffix)
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            IntType::U8 => "u8",
+            IntType::U16 => "u16",
+            IntType::U32 => "u32",
+            IntType::U64 => "u64",
+            IntType::U128 => "u128",
+            IntType::U256 => "u256",
+            IntType::Usize => "usize",
+            IntType::I8 => "i8",
+            IntType::I16 => "i16",
+            IntType::I32 => "i32",
+            IntType::I64 => "i64",
+            IntType::I128 => "i128",
+            IntType::Felt252 => "felt252",
+        }
+    }
+
+    /// Bit width, `None` for `felt252`.
+    pub fn bits(&self) -> Option<u32> {
+        match self {
+            IntType::U8 | IntType::I8 => Some(8),
+            IntType::U16 | IntType::I16 => Some(16),
+            IntType::U32 | IntType::I32 | IntType::Usize => Some(32),
+            IntType::U64 | IntType::I64 => Some(64),
+            IntType::U128 | IntType::I128 => Some(128),
+            IntType::U256 => Some(256),
+            IntType::Felt252 => None,
+        }
+    }
+
+    pub fn is_signed(&self) -> bool {
+        matches!(
+            self,
+            IntType::I8 | IntType::I16 | IntType::I32 | IntType::I64 | IntType::I128
+        )
+    }
+
+    /// Smallest value a literal of this type can have, negative `felt252` literals wrap
+    /// around the field prime.
+    pub fn min(&self) -> BigInt {
+        match (self.bits(), self.is_signed()) {
+            (Some(bits), true) => -(BigInt::one() << (bits - 1)),
+            (Some(_), false) => BigInt::ZERO,
+            (None, _) => -Felt::MAX.to_bigint(),
+        }
+    }
+
+    pub fn max(&self) -> BigInt {
+        match (self.bits(), self.is_signed()) {
+            (Some(bits), true) => (BigInt::one() << (bits - 1)) - 1,
+            (Some(bits), false) => (BigInt::one() << bits) - 1,
+            (None, _) => Felt::MAX.to_bigint(),
+        }
+    }
+
+    pub fn contains(&self, value: &BigInt) -> bool {
+        self.min() <= *value && *value <= self.max()
+    }
+}
+
+impl Display for IntType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for IntType {
+    type Err = LiteralError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        IntType::from_suffix(s).ok_or_else(|| LiteralError::UnknownSuffix(s.to_string()))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Error)]
+pub enum LiteralError {
+    #[error("invalid numeric literal `{0}`")]
+    Invalid(String),
+    #[error("unknown literal suffix `{0}`")]
+    UnknownSuffix(String),
+    #[error("{value} is out of range for {ty}")]
+    OutOfRange { value: BigInt, ty: IntType },
+    #[error("not a numeric literal")]
+    NotLiteral,
+    #[error("not a string literal")]
+    NotString,
+    #[error("invalid escape sequence `{0}`")]
+    InvalidEscape(String),
+    #[error("string literals must be ASCII")]
+    NonAscii,
+    #[error("short string is {0} bytes long, at most 31 fit in a felt252")]
+    ShortStringTooLong(usize),
+}
+
+/// A decoded numeric literal such as `0x1f_u8` or `-1000_i128`, or the value of a short string
+/// such as `'hello'_u128`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Literal {
+    pub value: BigInt,
+    pub suffix: Option<IntType>,
+}
+
+impl Literal {
+    pub fn new(value: impl Into<BigInt>, suffix: Option<IntType>) -> Self {
+        Literal {
+            value: value.into(),
+            suffix,
+        }
+    }
+
+    /// Decodes decimal, `0x`, `0o` and `0b` literals with optional `_` separators, a leading
+    /// `-` and a type suffix, checking the value fits the suffix type.
+    pub fn parse(text: &str) -> Result<Self, LiteralError> {
+        let invalid = || LiteralError::Invalid(text.to_string());
+        let (negative, digits) = match text.strip_prefix('-') {
+            Some(digits) => (true, digits.trim_start()),
+            None => (false, text),
+        };
+        let (digits, radix) = [("0x", 16), ("0o", 8), ("0b", 2)]
+            .into_iter()
+            .find_map(|(prefix, radix)| Some((digits.strip_prefix(prefix)?, radix)))
+            .unwrap_or((digits, 10));
+        if !digits.starts_with(|c: char| c.is_digit(radix)) {
+            return Err(invalid());
+        }
+        // Like the Cairo compiler, digits that happen to look like a suffix (`0x1_f32`) are
+        // part of the number.
+        let (value, suffix) = match BigInt::from_str_radix(digits, radix) {
+            Ok(value) => (value, None),
+            Err(_) => {
+                let (digits, suffix) = digits.rsplit_once('_').ok_or_else(invalid)?;
+                let value = BigInt::from_str_radix(digits, radix).map_err(|_| invalid())?;
+                (value, Some(suffix.parse()?))
+            }
+        };
+        let literal = Literal {
+            value: if negative { -value } else { value },
+            suffix,
+        };
+        literal.check_range()?;
+        Ok(literal)
+    }
+
+    /// Decodes a short string literal into its big endian numeric value.
+    pub fn from_short_string(text: &str) -> Result<Self, LiteralError> {
+        let (body, suffix) = split_quoted(text, '\'')?;
+        let value = unescape(body)?;
+        let bytes = short_string_bytes(&value)?;
+        let suffix = suffix.strip_prefix('_').unwrap_or(suffix);
+        let literal = Literal {
+            value: BigInt::from_bytes_be(Sign::Plus, bytes),
+            suffix: if suffix.is_empty() {
+                None
+            } else {
+                Some(suffix.parse()?)
+            },
+        };
+        literal.check_range()?;
+        Ok(literal)
+    }
+
+    pub fn check_range(&self) -> Result<(), LiteralError> {
+        match self.suffix {
+            Some(ty) if !ty.contains(&self.value) => Err(LiteralError::OutOfRange {
+                value: self.value.clone(),
+                ty,
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.value.is_negative()
+    }
+
+    /// The value as a field element, negative values wrap around the prime.
+    pub fn to_felt(&self) -> Felt {
+        Felt::from(&self.value)
+    }
+
+    pub fn to_u64(&self) -> Option<u64> {
+        self.value.to_u64()
+    }
+
+    pub fn to_u128(&self) -> Option<u128> {
+        self.value.to_u128()
+    }
+
+    pub fn to_i128(&self) -> Option<i128> {
+        self.value.to_i128()
+    }
+
+    /// The literal as an expression, negative values become a unary minus.
+    pub fn to_expr(&self) -> Expr {
+        let magnitude = Literal::new(self.value.abs(), self.suffix);
+        if self.is_negative() {
+            Expr::Unary(UnaryExpr {
+                op: UnaryOp::Minus,
+                expr: Box::new(Expr::Literal(magnitude.stringify())),
+                span: None,
+            })
+        } else {
+            Expr::Literal(magnitude.stringify())
+        }
+    }
+}
+
+/// Splits a quoted literal into its escaped body and whatever follows the closing quote.
+fn split_quoted(text: &str, quote: char) -> Result<(&str, &str), LiteralError> {
+    text.strip_prefix(quote)
+        .and_then(|rest| rest.rsplit_once(quote))
+        .ok_or(LiteralError::NotString)
+}
+
+fn unescape(body: &str) -> Result<String, LiteralError> {
+    let mut out = String::with_capacity(body.len());
+    let mut chars = body.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        let escaped = match chars.next() {
+            Some('n') => '\n',
+            Some('r') => '\r',
+            Some('t') => '\t',
+            Some('0') => '\0',
+            Some(c @ ('\\' | '\'' | '"')) => c,
+            Some('x') => {
+                let hex: String = chars.by_ref().take(2).collect();
+                u8::from_str_radix(&hex, 16)
+                    .map(char::from)
+                    .map_err(|_| LiteralError::InvalidEscape(format!("\\x{hex}")))?
+            }
+            Some(c) => return Err(LiteralError::InvalidEscape(format!("\\{c}"))),
+            None => return Err(LiteralError::InvalidEscape("\\".to_string())),
+        };
+        out.push(escaped);
+    }
+    if out.is_ascii() {
+        Ok(out)
+    } else {
+        Err(LiteralError::NonAscii)
+    }
+}
+
+fn escape(value: &str, quote: char) -> Result<String, LiteralError> {
+    if !value.is_ascii() {
+        return Err(LiteralError::NonAscii);
+    }
+    let mut out = String::with_capacity(value.len() + 2);
+    out.push(quote);
+    for c in value.chars() {
+        match c {
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            '\0' => out.push_str("\\0"),
+            '\\' => out.push_str("\\\\"),
+            c if c == quote => {
+                out.push('\\');
+                out.push(c);
+            }
+            c if c.is_ascii_control() => out.push_str(&format!("\\x{:02x}", c as u8)),
+            c => out.push(c),
+        }
+    }
+    out.push(quote);
+    Ok(out)
+}
+
+fn short_string_bytes(value: &str) -> Result<&[u8], LiteralError> {
+    match value.len() {
+        0..=31 => Ok(value.as_bytes()),
+        len => Err(LiteralError::ShortStringTooLong(len)),
+    }
+}
+
+/// Decodes the contents of a short string literal such as `'hello'`, ignoring any suffix.
+pub fn decode_short_string(text: &str) -> Result<String, LiteralError> {
+    let (body, _suffix) = split_quoted(text, '\'')?;
+    let value = unescape(body)?;
+    short_string_bytes(&value)?;
+    Ok(value)
+}
+
+/// Decodes the contents of a string literal such as `"hello\n"`.
+pub fn decode_string(text: &str) -> Result<String, LiteralError> {
+    let (body, _) = split_quoted(text, '"')?;
+    unescape(body)
+}
+
+/// Renders `value` as an escaped short string literal.
+pub fn encode_short_string(value: &str) -> Result<String, LiteralError> {
+    short_string_bytes(value)?;
+    escape(value, '\'')
+}
+
+/// Renders `value` as an escaped string literal.
+pub fn encode_string(value: &str) -> Result<String, LiteralError> {
+    escape(value, '"')
+}
+
+/// The value of a short string, `value` must be at most 31 ASCII bytes.
+pub fn short_string_to_felt(value: &str) -> Result<Felt, LiteralError> {
+    if !value.is_ascii() {
+        return Err(LiteralError::NonAscii);
+    }
+    Ok(Felt::from_bytes_be_slice(short_string_bytes(value)?))
+}
+
+/// The `core::byte_array::ByteArray` layout of a string: full 31 byte words followed by a
+/// pending word holding the remaining bytes.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ByteArray {
+    pub data: Vec<[u8; 31]>,
+    pub pending_word: Felt,
+    pub pending_word_len: usize,
+}
+
+impl ByteArray {
+    pub fn from_bytes(bytes: &[u8]) -> Self {
+        let chunks = bytes.chunks_exact(31);
+        let pending = chunks.remainder();
+        ByteArray {
+            data: chunks.map(|chunk| chunk.try_into().unwrap()).collect(),
+            pending_word: Felt::from_bytes_be_slice(pending),
+            pending_word_len: pending.len(),
+        }
+    }
+
+    /// Decodes a string literal such as `"hello"`.
+    pub fn from_literal(text: &str) -> Result<Self, LiteralError> {
+        Ok(ByteArray::from_bytes(decode_string(text)?.as_bytes()))
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let pending = self.pending_word.to_bytes_be();
+        let mut bytes: Vec<u8> = self.data.concat();
+        bytes.extend_from_slice(&pending[32 - self.pending_word_len.min(31)..]);
+        bytes
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.len() * 31 + self.pending_word_len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Expr {
+    /// A short string literal holding `value`.
+    pub fn short_string(value: &str) -> Result<Self, LiteralError> {
+        encode_short_string(value).map(Expr::ShortString)
+    }
+
+    /// A string literal holding `value`.
+    pub fn string(value: &str) -> Result<Self, LiteralError> {
+        encode_string(value).map(Expr::String)
+    }
+
+    /// The contents of a short string or string literal.
+    pub fn to_string_value(&self) -> Result<String, LiteralError> {
+        match self {
+            Expr::ShortString(text) => decode_short_string(text),
+            Expr::String(text) => decode_string(text),
+            _ => Err(LiteralError::NotString),
+        }
+    }
+
+    /// Decodes a numeric literal, including a negated one such as `-1_i8`, or the value of a
+    /// short string.
+    pub fn to_literal(&self) -> Result<Literal, LiteralError> {
+        match self {
+            Expr::Literal(text) => Literal::parse(text),
+            Expr::ShortString(text) => Literal::from_short_string(text),
+            Expr::Unary(UnaryExpr {
+                op: UnaryOp::Minus,
+                expr,
+                ..
+            }) => match expr.as_ref() {
+                Expr::Literal(text) => Literal::parse(&format!("-{text}")),
+                _ => Err(LiteralError::NotLiteral),
+            },
+            _ => Err(LiteralError::NotLiteral),
+        }
+    }
+}
+
+impl FromStr for Literal {
+    type Err = LiteralError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Literal::parse(s)
+    }
+}
+
+impl From<Felt> for Literal {
+    fn from(felt: Felt) -> Self {
+        Literal::new(felt.to_bigint(), None)
+    }
+}
+
+impl From<Literal> for Expr {
+    fn from(literal: Literal) -> Self {
+        literal.to_expr()
+    }
+}
+
+impl Pattern {
+    pub fn to_literal(&self) -> Result<Literal, LiteralError> {
+        match self {
+            Pattern::Literal(text) => Literal::parse(text),
+            Pattern::ShortString(text) => Literal::from_short_string(text),
+            _ => Err(LiteralError::NotLiteral),
+        }
+    }
+
+    /// The contents of a short string or string literal pattern.
+    pub fn to_string_value(&self) -> Result<String, LiteralError> {
+        match self {
+            Pattern::ShortString(text) => decode_short_string(text),
+            Pattern::String(text) => decode_string(text),
+            _ => Err(LiteralError::NotString),
+        }
+    }
+}
@@ -5,6 +5,7 @@ use crate::{
 };
 
 syntax_type! { MacroDeclarationItem[ItemMacroDeclaration]{
+        docs: Vec<String>,
         attributes: Vec<Attribute>,
         visibility: Visibility,
         name: String,
@@ -0,0 +1,257 @@
+use cairo_lang_macro::TokenStream;
+
+use crate::diagnostic::strict;
+use crate::visit::{VisitMut, Visitable};
+use crate::{
+    Attribute, Expr, ExprPath, GenericParam, Item, ParseDiagnostic, ParseError, ParseResult,
+    Pattern, Span, Statement, Type, items_from_token_stream_with_diagnostics, str_to_token_stream,
+};
+
+/// A node that can be parsed on its own from a snippet of Cairo code.
+///
+/// Snippets other than items are parsed inside a small item template, spans of the result and
+/// of diagnostics are relative to the snippet.
+pub trait Parse: Sized {
+    /// What the snippet must contain, used in [`ParseError::Expected`].
+    const EXPECTED: &'static str;
+
+    /// Parses the snippet, returning the node if it has the right shape along with every
+    /// diagnostic the parser emitted.
+    fn parse_with_diagnostics(code: &str) -> (Option<Self>, Vec<ParseDiagnostic>);
+
+    /// Strict variant of [`Parse::parse_with_diagnostics`], failing if any diagnostic was
+    /// emitted.
+    fn parse(code: &str) -> ParseResult<Self> {
+        strict(Self::parse_with_diagnostics(code))?.ok_or(ParseError::Expected(Self::EXPECTED))
+    }
+}
+
+/// Moves spans from template offsets to snippet offsets, clamping them to the snippet.
+struct SpanShift {
+    offset: u32,
+    len: u32,
+}
+
+impl SpanShift {
+    fn shift(&self, span: Span) -> Span {
+        let map = |offset: u32| offset.saturating_sub(self.offset).min(self.len);
+        Span::new(map(span.start), map(span.end))
+    }
+}
+
+impl VisitMut for SpanShift {
+    fn visit_span_mut(&mut self, span: &mut Option<Span>) {
+        *span = span.map(|span| self.shift(span));
+    }
+}
+
+/// Parses `code` placed between `prefix` and `suffix`, which must form a single item.
+fn parse_wrapped<T: Visitable>(
+    prefix: &str,
+    code: &str,
+    suffix: &str,
+    extract: impl FnOnce(Item) -> Option<T>,
+) -> (Option<T>, Vec<ParseDiagnostic>) {
+    let wrapped = format!("{prefix}{code}{suffix}");
+    let (mut items, diagnostics) =
+        items_from_token_stream_with_diagnostics(str_to_token_stream(&wrapped));
+    let mut shift = SpanShift {
+        offset: prefix.len() as u32,
+        len: code.len() as u32,
+    };
+    let diagnostics = diagnostics
+        .into_iter()
+        .map(|d| ParseDiagnostic {
+            span: shift.shift(d.span),
+            ..d
+        })
+        .collect();
+    let mut node = match items.len() {
+        1 => extract(items.remove(0)),
+        _ => None,
+    };
+    node.accept_mut(&mut shift);
+    (node, diagnostics)
+}
+
+fn function_body(item: Item) -> Option<Vec<Statement>> {
+    match item {
+        Item::FreeFunction(function) => Some(function.body),
+        _ => None,
+    }
+}
+
+fn single<T>(mut nodes: Vec<T>) -> Option<T> {
+    match nodes.len() {
+        1 => nodes.pop(),
+        _ => None,
+    }
+}
+
+impl Parse for Vec<Item> {
+    const EXPECTED: &'static str = "items";
+
+    fn parse_with_diagnostics(code: &str) -> (Option<Self>, Vec<ParseDiagnostic>) {
+        let (items, diagnostics) =
+            items_from_token_stream_with_diagnostics(str_to_token_stream(code));
+        (Some(items), diagnostics)
+    }
+}
+
+impl Parse for Item {
+    const EXPECTED: &'static str = "a single item";
+
+    fn parse_with_diagnostics(code: &str) -> (Option<Self>, Vec<ParseDiagnostic>) {
+        let (items, diagnostics) = Vec::<Item>::parse_with_diagnostics(code);
+        (items.and_then(single), diagnostics)
+    }
+}
+
+impl Parse for Vec<Statement> {
+    const EXPECTED: &'static str = "statements";
+
+    fn parse_with_diagnostics(code: &str) -> (Option<Self>, Vec<ParseDiagnostic>) {
+        parse_wrapped("fn __parse() {\n", code, "\n}", function_body)
+    }
+}
+
+impl Parse for Statement {
+    const EXPECTED: &'static str = "a single statement";
+
+    fn parse_with_diagnostics(code: &str) -> (Option<Self>, Vec<ParseDiagnostic>) {
+        parse_wrapped("fn __parse() {\n", code, "\n}", |item| {
+            function_body(item).and_then(single)
+        })
+    }
+}
+
+impl Parse for Expr {
+    const EXPECTED: &'static str = "an expression";
+
+    fn parse_with_diagnostics(code: &str) -> (Option<Self>, Vec<ParseDiagnostic>) {
+        parse_wrapped(
+            "fn __parse() {\n",
+            code,
+            "\n}",
+            |item| match function_body(item).and_then(single)? {
+                Statement::Expr(statement)
+                    if !statement.semicolon && statement.attributes.is_empty() =>
+                {
+                    Some(statement.expr)
+                }
+                _ => None,
+            },
+        )
+    }
+}
+
+impl Parse for Pattern {
+    const EXPECTED: &'static str = "a pattern";
+
+    fn parse_with_diagnostics(code: &str) -> (Option<Self>, Vec<ParseDiagnostic>) {
+        parse_wrapped(
+            "fn __parse() {\nlet ",
+            code,
+            " = 0;\n}",
+            |item| match function_body(item).and_then(single)? {
+                Statement::Let(statement) => Some(statement.pattern),
+                _ => None,
+            },
+        )
+    }
+}
+
+impl Parse for Attribute {
+    const EXPECTED: &'static str = "a single attribute";
+
+    fn parse_with_diagnostics(code: &str) -> (Option<Self>, Vec<ParseDiagnostic>) {
+        parse_wrapped("", code, "\nfn __parse() {}", |item| match item {
+            Item::FreeFunction(function) => single(function.attributes),
+            _ => None,
+        })
+    }
+}
+
+impl Parse for GenericParam {
+    const EXPECTED: &'static str = "a single generic parameter";
+
+    fn parse_with_diagnostics(code: &str) -> (Option<Self>, Vec<ParseDiagnostic>) {
+        parse_wrapped("fn __parse<", code, ">() {}", |item| match item {
+            Item::FreeFunction(function) => single(function.declaration.generic_params?),
+            _ => None,
+        })
+    }
+}
+
+impl Parse for ExprPath {
+    const EXPECTED: &'static str = "a path";
+
+    /// Parses a path in type position, where generic arguments may omit the `::`, as in
+    /// `core::array::Array<felt252>`.
+    fn parse_with_diagnostics(code: &str) -> (Option<Self>, Vec<ParseDiagnostic>) {
+        parse_wrapped("type __Parse = ", code, ";", |item| match item {
+            Item::TypeAlias(alias) => match Expr::from(alias.ty) {
+                Expr::Path(path) => Some(path),
+                _ => None,
+            },
+            _ => None,
+        })
+    }
+}
+
+impl Parse for Type {
+    const EXPECTED: &'static str = "a type";
+
+    /// Parses a type, for example the value of `#[key(type = "u256")]`.
+    fn parse_with_diagnostics(code: &str) -> (Option<Self>, Vec<ParseDiagnostic>) {
+        parse_wrapped("type __Parse = ", code, ";", |item| match item {
+            Item::TypeAlias(alias) => Some(alias.ty),
+            _ => None,
+        })
+    }
+}
+
+macro_rules! parse_functions {
+    ($($name:ident: $node:ty),* $(,)?) => {
+        paste::paste! {
+            $(
+                #[doc = concat!("Parses a snippet as `", stringify!($node), "`, see [`Parse`].")]
+                pub fn [<parse_ $name>](code: &str) -> ParseResult<$node> {
+                    <$node as Parse>::parse(code)
+                }
+
+                #[doc = concat!("Parses a snippet as `", stringify!($node), "` along with its diagnostics.")]
+                pub fn [<parse_ $name _with_diagnostics>](
+                    code: &str,
+                ) -> (Option<$node>, Vec<ParseDiagnostic>) {
+                    <$node as Parse>::parse_with_diagnostics(code)
+                }
+
+                #[doc = concat!("Parses a token stream as `", stringify!($node), "`, failing on any diagnostic.")]
+                pub fn [<try_ $name _from_token_stream>](
+                    token_stream: TokenStream,
+                ) -> ParseResult<$node> {
+                    <$node as Parse>::parse(&token_stream.to_string())
+                }
+
+                #[doc = concat!("Parses a token stream as `", stringify!($node), "` along with its diagnostics.")]
+                pub fn [<$name _from_token_stream_with_diagnostics>](
+                    token_stream: TokenStream,
+                ) -> (Option<$node>, Vec<ParseDiagnostic>) {
+                    <$node as Parse>::parse_with_diagnostics(&token_stream.to_string())
+                }
+            )*
+        }
+    };
+}
+
+parse_functions! {
+    expr: Expr,
+    statement: Statement,
+    statements: Vec<Statement>,
+    pattern: Pattern,
+    attribute: Attribute,
+    generic_param: GenericParam,
+    path: ExprPath,
+    type: Type,
+}
@@ -0,0 +1,201 @@
+use crate::parse::Parse;
+use crate::{CairoFormat, CairoWrite, ParseError, ParseResult, Slice};
+
+/// Builds AST nodes from a Cairo template, interpolating `#var` and `#(...)sep*` repetitions.
+///
+/// Values are bound by name, either from a variable of the same name or with `name = expr`.
+/// Single values can be anything implementing [`CairoWrite`], repetitions iterate over
+/// slices and vectors of them, which are an error outside of a repetition. The result is checked by the parser and can be any
+/// [`Parse`] type.
+///
+/// ```ignore
+/// let item: Item = cairo_quote!(
+///     "fn #name(#(#params),*) -> felt252 { #body }",
+///     name = "get",
+///     params,
+///     body,
+/// )?;
+/// ```
+#[macro_export]
+macro_rules! cairo_quote {
+    ($template:expr $(, $name:ident $(= $value:expr)?)* $(,)?) => {{
+        #[allow(unused_imports)]
+        use $crate::quote::{QuoteRepeated as _, QuoteSingle as _};
+        $crate::quote::quote(
+            $template,
+            &[$((stringify!($name), (&&$crate::cairo_quote!(@value $name $(= $value)?)).quoted())),*],
+        )
+    }};
+    (@value $name:ident = $value:expr) => {
+        $value
+    };
+    (@value $name:ident) => {
+        $name
+    };
+}
+
+/// A value bound to a template variable.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Quoted {
+    Single(String),
+    Repeated(Vec<String>),
+}
+
+#[doc(hidden)]
+pub trait QuoteSingle {
+    fn quoted(&self) -> Quoted;
+}
+
+impl<T: CairoWrite> QuoteSingle for T {
+    fn quoted(&self) -> Quoted {
+        Quoted::Single(self.stringify())
+    }
+}
+
+/// Takes precedence over [`QuoteSingle`] in [`cairo_quote!`] through auto-ref, so that
+/// collections which are also [`CairoWrite`] can still be repeated.
+#[doc(hidden)]
+pub trait QuoteRepeated {
+    fn quoted(&self) -> Quoted;
+}
+
+impl<S> QuoteRepeated for &S
+where
+    S: Slice + ?Sized,
+    S::Element: CairoWrite,
+{
+    fn quoted(&self) -> Quoted {
+        Quoted::Repeated(self.elements().iter().map(|e| e.stringify()).collect())
+    }
+}
+
+/// Expands the template and parses the result, see [`cairo_quote!`].
+pub fn quote<T: Parse>(template: &str, vars: &[(&str, Quoted)]) -> ParseResult<T> {
+    T::parse(&expand(template, vars)?)
+}
+
+/// Substitutes the variables into the template without parsing it.
+pub fn expand(template: &str, vars: &[(&str, Quoted)]) -> ParseResult<String> {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    let mut literal = None;
+    while let Some(c) = rest.chars().next() {
+        rest = &rest[c.len_utf8()..];
+        match (literal, c) {
+            (Some(_), '\\') => {
+                out.push(c);
+                if let Some(escaped) = rest.chars().next() {
+                    out.push(escaped);
+                    rest = &rest[escaped.len_utf8()..];
+                }
+                continue;
+            }
+            (Some(quote), c) if c == quote => literal = None,
+            (None, '"' | '\'') => literal = Some(c),
+            (None, '#') if rest.starts_with('(') => {
+                let (body, separator, tail) = split_repetition(rest)?;
+                out.push_str(&expand_repetition(body, separator, vars)?);
+                rest = tail;
+                continue;
+            }
+            (None, '#') if rest.starts_with(is_ident_start) => {
+                let end = rest.find(|c| !is_ident_char(c)).unwrap_or(rest.len());
+                let name = &rest[..end];
+                match lookup(vars, name)? {
+                    Quoted::Single(value) => out.push_str(value),
+                    Quoted::Repeated(_) => {
+                        return Err(quote_error(format!(
+                            "`#{name}` holds several values, use it inside a `#(...)*` repetition"
+                        )));
+                    }
+                }
+                rest = &rest[end..];
+                continue;
+            }
+            _ => {}
+        }
+        out.push(c);
+    }
+    Ok(out)
+}
+
+/// Splits `(body)sep*tail` into its parts, `rest` starting at the opening parenthesis.
+fn split_repetition(rest: &str) -> ParseResult<(&str, &str, &str)> {
+    let mut depth = 0;
+    let close = rest
+        .char_indices()
+        .find(|&(_, c)| {
+            match c {
+                '(' => depth += 1,
+                ')' => depth -= 1,
+                _ => {}
+            }
+            depth == 0
+        })
+        .map(|(i, _)| i)
+        .ok_or_else(|| quote_error("unclosed `#(` repetition"))?;
+    let after = &rest[close + 1..];
+    let star = after
+        .find('*')
+        .filter(|&i| !after[..i].contains(char::is_whitespace))
+        .ok_or_else(|| quote_error("expected `*` after `#(...)` repetition"))?;
+    Ok((&rest[1..close], &after[..star], &after[star + 1..]))
+}
+
+fn expand_repetition(body: &str, separator: &str, vars: &[(&str, Quoted)]) -> ParseResult<String> {
+    let mut count = None;
+    for name in referenced_vars(body) {
+        if let Quoted::Repeated(values) = lookup(vars, name)? {
+            match count {
+                Some(n) if n != values.len() => {
+                    return Err(quote_error(format!(
+                        "`#{name}` has {} values, expected {n}",
+                        values.len()
+                    )));
+                }
+                _ => count = Some(values.len()),
+            }
+        }
+    }
+    let count = count.ok_or_else(|| quote_error("repetition contains no repeated variable"))?;
+    let expanded = (0..count)
+        .map(|i| {
+            let vars: Vec<(&str, Quoted)> = vars
+                .iter()
+                .map(|(name, value)| match value {
+                    Quoted::Repeated(values) => (*name, Quoted::Single(values[i].clone())),
+                    single => (*name, single.clone()),
+                })
+                .collect();
+            expand(body, &vars)
+        })
+        .collect::<ParseResult<Vec<_>>>()?;
+    Ok(expanded.join(separator))
+}
+
+fn referenced_vars(body: &str) -> impl Iterator<Item = &str> {
+    body.match_indices('#').filter_map(|(i, _)| {
+        let name = &body[i + 1..];
+        let end = name.find(|c| !is_ident_char(c)).unwrap_or(name.len());
+        name.starts_with(is_ident_start).then(|| &name[..end])
+    })
+}
+
+fn lookup<'a>(vars: &'a [(&str, Quoted)], name: &str) -> ParseResult<&'a Quoted> {
+    vars.iter()
+        .find(|(var, _)| *var == name)
+        .map(|(_, value)| value)
+        .ok_or_else(|| quote_error(format!("unbound variable `#{name}`")))
+}
+
+fn is_ident_start(c: char) -> bool {
+    c.is_ascii_alphabetic() || c == '_'
+}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_'
+}
+
+fn quote_error(message: impl Into<String>) -> ParseError {
+    ParseError::Quote(message.into())
+}
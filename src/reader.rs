@@ -1,15 +1,88 @@
+use crate::ast::missing_node_name;
+use crate::diagnostic::{ParseDiagnostic, Severity, collect_diagnostics};
 use crate::utils::str_to_token_stream;
-use crate::{CairoFormat, items_from_token_stream};
-use cairo_lang_macro::{ProcMacroResult, TokenStream, attribute_macro};
+use crate::{CairoFormat, FromAst, Item};
+use cairo_lang_macro::{
+    Diagnostic, Diagnostics, ProcMacroResult, Severity as MacroSeverity, TokenStream,
+    attribute_macro,
+};
+use cairo_lang_parser::utils::SimpleParserDatabase;
+use cairo_lang_syntax::node::SyntaxNode;
+use cairo_lang_syntax::node::ast::SyntaxFile;
+use salsa::Database;
 
 #[attribute_macro]
 pub fn parse(_attr: TokenStream, code: TokenStream) -> ProcMacroResult {
-    // let db = SimpleParserDatabase::default();
-    // let (node, _diagnostics) = db.parse_virtual_with_diagnostics(code);
-    let items = items_from_token_stream(code);
-    let string: String = items.stringify();
-    let stream = str_to_token_stream(&string);
-    println!("PARSE MACRO RAN");
-    println!("{string}");
-    ProcMacroResult::new(stream)
+    let (items, diagnostics) = parse_macro_input(&code);
+    macro_result(code, &items, diagnostics)
+}
+
+/// Parses the body of a macro, reporting parse errors and `Missing` nodes as diagnostics located
+/// in `token_stream`.
+pub fn parse_macro_input(token_stream: &TokenStream) -> (Vec<Item>, Diagnostics) {
+    let db = SimpleParserDatabase::default();
+    let (node, diagnostics) = db.parse_virtual_with_diagnostics(token_stream);
+    let mut diagnostics = collect_diagnostics(&db, diagnostics);
+    diagnostics.extend(node_diagnostics(&db, node, &diagnostics));
+    let items = FromAst::<SyntaxFile>::from_syntax_node(&db, node);
+    (items, to_macro_diagnostics(&diagnostics, token_stream))
+}
+
+/// Emits `output` unless `diagnostics` contains an error, in which case `input` is returned
+/// unchanged so the compiler only reports the diagnostics.
+pub fn macro_result<T: CairoFormat>(
+    input: TokenStream,
+    output: &T,
+    diagnostics: Diagnostics,
+) -> ProcMacroResult {
+    let diagnostics: Vec<Diagnostic> = diagnostics.into_iter().collect();
+    let stream = if diagnostics
+        .iter()
+        .any(|d| d.severity() == MacroSeverity::Error)
+    {
+        input
+    } else {
+        str_to_token_stream(&output.stringify())
+    };
+    ProcMacroResult::new(stream).with_diagnostics(diagnostics.into())
+}
+
+pub fn to_macro_diagnostics(
+    diagnostics: &[ParseDiagnostic],
+    token_stream: &TokenStream,
+) -> Diagnostics {
+    diagnostics
+        .iter()
+        .map(|d| {
+            let severity = match d.severity {
+                Severity::Error => MacroSeverity::Error,
+                Severity::Warning => MacroSeverity::Warning,
+            };
+            Diagnostic::spanned(
+                d.span.to_token_stream_span(token_stream),
+                severity,
+                &d.message,
+            )
+        })
+        .collect()
+}
+
+fn node_diagnostics<'db>(
+    db: &'db dyn Database,
+    root: SyntaxNode<'db>,
+    reported: &[ParseDiagnostic],
+) -> Vec<ParseDiagnostic> {
+    // Missing nodes are a by-product of parser errors, only report them on their own.
+    if reported.iter().any(ParseDiagnostic::is_error) {
+        return vec![];
+    }
+    root.descendants(db)
+        .filter_map(|node| {
+            missing_node_name(node.kind(db)).map(|name| ParseDiagnostic {
+                message: format!("Missing {name}."),
+                severity: Severity::Error,
+                span: node.span_without_trivia(db).into(),
+            })
+        })
+        .collect()
 }
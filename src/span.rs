@@ -0,0 +1,78 @@
+use cairo_lang_filesystem::span::TextSpan;
+use cairo_lang_macro::{TextSpan as MacroTextSpan, TokenStream, TokenTree};
+use cairo_lang_syntax::node::TypedSyntaxNode;
+use salsa::Database;
+
+/// Byte offset range into the parsed source text.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Span {
+    pub start: u32,
+    pub end: u32,
+}
+
+impl Span {
+    pub fn new(start: u32, end: u32) -> Self {
+        Self { start, end }
+    }
+
+    pub fn len(&self) -> u32 {
+        self.end.saturating_sub(self.start)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+}
+
+impl Span {
+    /// Maps a span over the text of `token_stream` onto the original spans of its tokens.
+    pub fn to_token_stream_span(&self, token_stream: &TokenStream) -> MacroTextSpan {
+        MacroTextSpan::new(
+            map_offset(token_stream, self.start),
+            map_offset(token_stream, self.end),
+        )
+    }
+}
+
+fn map_offset(token_stream: &TokenStream, offset: u32) -> u32 {
+    let mut token_start = 0;
+    let mut last_end = 0;
+    for TokenTree::Ident(token) in &token_stream.tokens {
+        let token_end = token_start + token.content.as_ref().len() as u32;
+        if offset < token_end {
+            let width = token.span.end.saturating_sub(token.span.start);
+            return token.span.start + (offset.saturating_sub(token_start)).min(width);
+        }
+        token_start = token_end;
+        last_end = token.span.end;
+    }
+    last_end
+}
+
+impl From<TextSpan> for Span {
+    fn from(span: TextSpan) -> Self {
+        Span::new(span.start.as_u32(), span.end.as_u32())
+    }
+}
+
+/// Nodes that remember where in the source they were parsed from.
+pub trait Spanned {
+    fn span(&self) -> Option<Span>;
+
+    /// The span in the macro input, `token_stream` being the stream the node was parsed from.
+    fn text_span(&self, token_stream: &TokenStream) -> Option<MacroTextSpan> {
+        self.span()
+            .map(|span| span.to_token_stream_span(token_stream))
+    }
+}
+
+impl<T: Spanned> Spanned for Box<T> {
+    fn span(&self) -> Option<Span> {
+        T::span(self)
+    }
+}
+
+pub fn node_span<'db, T: TypedSyntaxNode<'db>>(ast: &T, db: &'db dyn Database) -> Span {
+    ast.as_syntax_node().span_without_trivia(db).into()
+}
@@ -1,6 +1,7 @@
 use crate::{
-    Attribute, Expr, ExprPath, Identifier, Item, Modifier, from_typed_syntax_node, syntax_enum,
-    syntax_option, syntax_terminal_bool, syntax_type, vec_from_element_list,
+    Attribute, Expr, ExprPath, Identifier, Item, Modifier, Span, Spanned, Type,
+    from_typed_syntax_node, syntax_enum, syntax_option, syntax_terminal_bool, syntax_type,
+    vec_from_element_list,
 };
 
 syntax_enum! {
@@ -59,7 +60,7 @@ syntax_type! {
     LetStatement[StatementLet]{
         attributes: Vec<Attribute>,
         pattern: Pattern,
-        type_clause: Option<Expr>,
+        type_clause: Option<Type>,
         rhs: Expr,
         let_else_clause: Option<Vec<Statement>>,
     }
@@ -132,3 +133,17 @@ syntax_option! {OptionLetElseClause{LetElseClause: Vec<Statement>}}
 from_typed_syntax_node! {PatternEnumInnerPattern.pattern, Pattern}
 from_typed_syntax_node! {ConditionExpr.expr, Expr}
 from_typed_syntax_node! {StatementItem.item, Item}
+
+impl Spanned for Statement {
+    fn span(&self) -> Option<Span> {
+        match self {
+            Statement::Let(stmt) => stmt.span(),
+            Statement::Expr(stmt) => stmt.span(),
+            Statement::Continue(stmt) => stmt.span(),
+            Statement::Return(stmt) => stmt.span(),
+            Statement::Break(stmt) => stmt.span(),
+            Statement::Item(item) => item.span(),
+            Statement::Missing => None,
+        }
+    }
+}
@@ -0,0 +1,258 @@
+use cairo_lang_syntax::node::ast;
+use salsa::Database;
+
+use crate::ast::checked_from_ast;
+use crate::expr::{UnaryExpr, UnaryOp};
+use crate::span::node_span;
+use crate::{
+    AstInto, AstResult, Expr, ExprPath, FixedSizeArray, FromAst, PathSegment, Span, Spanned,
+    TryFromAst, from_typed_syntax_node, syntax_display, syntax_option, syntax_type,
+    vec_from_element_list,
+};
+
+/// The type of a member, parameter, return value, constant or alias.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Type {
+    /// A named type such as `felt252` or `Array<T>`, generic arguments are part of the path.
+    Path(ExprPath),
+    /// A tuple with at least one element.
+    Tuple(TupleType),
+    /// The empty tuple `()`.
+    Unit,
+    /// `@T`.
+    Snapshot(Box<Type>),
+    /// `&T`.
+    Reference(Box<Type>),
+    /// `[T; N]`.
+    FixedSizeArray(FixedSizeArrayType),
+    /// `_`, left for the compiler to infer.
+    Infer,
+    /// The `never` type.
+    Never,
+    /// An expression the parser accepted in type position that is none of the above, kept
+    /// as is.
+    Expr(Box<Expr>),
+    Missing,
+}
+
+syntax_type! {
+    TupleType[ExprListParenthesized] {
+        types[expressions]: Vec<Type>,
+    }
+}
+
+// Read from an `ExprFixedSizeArray` holding a single type, see `Type::from_ast`.
+syntax_type! {
+    @node FixedSizeArrayType {
+        ty: Box<Type>,
+        size: Box<Expr>,
+    }
+}
+syntax_display!(FixedSizeArrayType);
+
+impl Type {
+    pub fn path(path: ExprPath) -> Self {
+        match path.path.as_slice() {
+            [PathSegment::Simple(name)] if !path.dollar && name == "never" => Type::Never,
+            _ => Type::Path(path),
+        }
+    }
+
+    pub fn tuple(elements: Vec<Type>) -> Self {
+        if elements.is_empty() {
+            Type::Unit
+        } else {
+            Type::Tuple(TupleType {
+                types: elements,
+                span: None,
+            })
+        }
+    }
+
+    /// `[ty; size]`.
+    pub fn fixed_size_array(ty: Type, size: Expr) -> Self {
+        Type::FixedSizeArray(FixedSizeArrayType {
+            ty: Box::new(ty),
+            size: Box::new(size),
+            span: None,
+        })
+    }
+
+    pub fn is_unit(&self) -> bool {
+        matches!(self, Type::Unit)
+    }
+
+    /// The path of a [`Type::Path`], looking through snapshots and references.
+    pub fn base_path(&self) -> Option<&ExprPath> {
+        match self {
+            Type::Path(path) => Some(path),
+            Type::Snapshot(ty) | Type::Reference(ty) => ty.base_path(),
+            _ => None,
+        }
+    }
+}
+
+impl<'db> FromAst<'db, ast::Expr<'db>> for Type {
+    fn from_ast(ast: ast::Expr<'db>, db: &'db dyn Database) -> Self {
+        let fallback = |ast: ast::Expr<'db>| Type::Expr(Box::new(ast.ast_into(db)));
+        match ast.clone() {
+            ast::Expr::Path(path) => Type::path(path.ast_into(db)),
+            ast::Expr::Tuple(tuple) => {
+                let tuple: TupleType = tuple.ast_into(db);
+                if tuple.types.is_empty() {
+                    Type::Unit
+                } else {
+                    Type::Tuple(tuple)
+                }
+            }
+            ast::Expr::Unary(unary) => {
+                let ty = Box::new(unary.expr(db).ast_into(db));
+                match unary.op(db) {
+                    ast::UnaryOperator::At(_) => Type::Snapshot(ty),
+                    ast::UnaryOperator::Reference(_) => Type::Reference(ty),
+                    _ => fallback(ast),
+                }
+            }
+            ast::Expr::FixedSizeArray(array) => {
+                let mut types: Vec<Type> = array.exprs(db).ast_into(db);
+                let size: Option<Box<Expr>> = array.size(db).ast_into(db);
+                match (types.len(), size) {
+                    (1, Some(size)) => Type::FixedSizeArray(FixedSizeArrayType {
+                        ty: Box::new(types.remove(0)),
+                        size,
+                        span: Some(node_span(&array, db)),
+                    }),
+                    _ => fallback(ast),
+                }
+            }
+            ast::Expr::Underscore(_) => Type::Infer,
+            ast::Expr::Missing(_) => Type::Missing,
+            _ => fallback(ast),
+        }
+    }
+}
+
+impl<'db> TryFromAst<'db, ast::Expr<'db>> for Type {
+    fn try_from_ast(ast: ast::Expr<'db>, db: &'db dyn Database) -> AstResult<Self> {
+        checked_from_ast(ast, db)
+    }
+}
+
+from_typed_syntax_node! {TypeClause.ty, Type}
+from_typed_syntax_node! {ReturnTypeClause.ty, Type}
+
+syntax_option! {OptionTypeClause {TypeClause: Type}}
+syntax_option! {OptionReturnTypeClause {ReturnTypeClause: Type}}
+
+vec_from_element_list! {ExprList, Type}
+
+impl Spanned for Type {
+    fn span(&self) -> Option<Span> {
+        match self {
+            Type::Path(path) => path.span(),
+            Type::Tuple(tuple) => tuple.span,
+            Type::FixedSizeArray(array) => array.span,
+            Type::Snapshot(ty) | Type::Reference(ty) => ty.span(),
+            Type::Expr(expr) => expr.span(),
+            _ => None,
+        }
+    }
+}
+
+impl From<ExprPath> for Type {
+    fn from(path: ExprPath) -> Self {
+        Type::path(path)
+    }
+}
+
+impl From<Type> for Expr {
+    fn from(ty: Type) -> Self {
+        let unary = |op, ty: Box<Type>| {
+            Expr::Unary(UnaryExpr {
+                op,
+                expr: Box::new((*ty).into()),
+                span: None,
+            })
+        };
+        match ty {
+            Type::Path(path) => Expr::Path(path),
+            Type::Tuple(tuple) => Expr::Tuple(tuple.types.into_iter().map(Into::into).collect()),
+            Type::Unit => Expr::Tuple(vec![]),
+            Type::Snapshot(ty) => unary(UnaryOp::At, ty),
+            Type::Reference(ty) => unary(UnaryOp::Reference, ty),
+            Type::FixedSizeArray(FixedSizeArrayType { ty, size, span }) => {
+                Expr::FixedSizeArray(FixedSizeArray {
+                    exprs: vec![(*ty).into()],
+                    size: Some(size),
+                    span,
+                })
+            }
+            Type::Infer => Expr::Underscore,
+            Type::Never => Expr::Path(ExprPath {
+                dollar: false,
+                path: vec![PathSegment::Simple("never".to_string())],
+                span: None,
+            }),
+            Type::Expr(expr) => *expr,
+            Type::Missing => Expr::Missing,
+        }
+    }
+}
+
+impl TryFrom<Expr> for Type {
+    type Error = Expr;
+
+    /// Reads an expression in type position, returning it unchanged if it is not a type.
+    fn try_from(expr: Expr) -> Result<Self, Expr> {
+        if is_type_expr(&expr) {
+            Ok(type_from_expr(expr))
+        } else {
+            Err(expr)
+        }
+    }
+}
+
+fn is_type_expr(expr: &Expr) -> bool {
+    match expr {
+        Expr::Path(_) | Expr::Underscore | Expr::Missing => true,
+        Expr::Tuple(exprs) => exprs.iter().all(is_type_expr),
+        Expr::Unary(unary) => {
+            matches!(unary.op, UnaryOp::At | UnaryOp::Reference) && is_type_expr(&unary.expr)
+        }
+        Expr::FixedSizeArray(array) => {
+            array.exprs.len() == 1 && array.size.is_some() && is_type_expr(&array.exprs[0])
+        }
+        _ => false,
+    }
+}
+
+/// Converts an expression already checked by [`is_type_expr`].
+fn type_from_expr(expr: Expr) -> Type {
+    match expr {
+        Expr::Path(path) => Type::path(path),
+        Expr::Tuple(exprs) => Type::tuple(exprs.into_iter().map(type_from_expr).collect()),
+        Expr::Unary(unary) => {
+            let ty = Box::new(type_from_expr(*unary.expr));
+            match unary.op {
+                UnaryOp::At => Type::Snapshot(ty),
+                _ => Type::Reference(ty),
+            }
+        }
+        Expr::FixedSizeArray(FixedSizeArray { exprs, size, span }) => {
+            Type::FixedSizeArray(FixedSizeArrayType {
+                ty: Box::new(
+                    exprs
+                        .into_iter()
+                        .next()
+                        .map_or(Type::Missing, type_from_expr),
+                ),
+                size: size.unwrap_or(Box::new(Expr::Missing)),
+                span,
+            })
+        }
+        Expr::Underscore => Type::Infer,
+        Expr::Missing => Type::Missing,
+        expr => Type::Expr(Box::new(expr)),
+    }
+}
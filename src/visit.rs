@@ -0,0 +1,420 @@
+use crate::attribute::{Arg, ArgClause, Attribute, NamedArg};
+use crate::common::{Identifier, Modifier, Param, Visibility};
+use crate::expr::{
+    BinaryExpr, BinaryOp, Closure, ElseIfBlock, Expr, ExprPath, FixedSizeArray, ForExpr,
+    FunctionCall, GenericArg, GenericArgNamed, IfExpr, IndexExpr, InlineMacroExpr, LoopExpr,
+    MatchArm, MatchExpr, PathSegment, PathSegmentWithGenerics, StructArg, StructArgSingle,
+    StructConstructorCall, UnaryExpr, UnaryOp, WhileExpr,
+};
+use crate::generic_param::{
+    AssociatedItemConstraint, ConstGenericParam, GenericParam, ImplAnonymousGenericParam,
+    ImplNamedGenericParam,
+};
+use crate::item::{
+    Constant, Enum, ExternFunction, ExternType, FunctionDeclaration, FunctionSignature,
+    FunctionWithBody, Impl, ImplAlias, ImplItem, InlineMacroItem, Item, Member, Module, Struct,
+    Trait, TraitConstant, TraitFunction, TraitImpl, TraitItem, TraitType, TypeAlias, UseItem,
+    UsePath, UsePathLeaf, UsePathSingle, Variant,
+};
+use crate::macros::{
+    MacroDeclarationItem, MacroElement, MacroParam, MacroParamKind, MacroRepetition,
+    MacroRepetitionOperator, MacroRule, TokenNode, WrappedMacro,
+};
+use crate::statement::{
+    BreakStatement, Condition, ContinueStatement, ExprStatement, LetCondition, LetStatement,
+    ParamWithPatten, Pattern, PatternEnum, PatternStruct, PatternStructParam, ReturnStatement,
+    Statement,
+};
+use crate::ty::{FixedSizeArrayType, TupleType};
+use crate::{Span, Type};
+
+/// A node of the owned AST that can be walked by a [`Visit`], [`VisitMut`] or [`Fold`].
+pub trait Visitable: Sized {
+    /// Calls the visitor method for this node's type.
+    fn accept<V: Visit + ?Sized>(&self, visitor: &mut V);
+    /// Visits the node's children in source order.
+    fn walk<V: Visit + ?Sized>(&self, visitor: &mut V);
+    /// Calls the mutable visitor method for this node's type.
+    fn accept_mut<V: VisitMut + ?Sized>(&mut self, visitor: &mut V);
+    /// Visits the node's children in source order, allowing them to be modified in place.
+    fn walk_mut<V: VisitMut + ?Sized>(&mut self, visitor: &mut V);
+    /// Calls the folder method for this node's type.
+    fn fold<F: Fold + ?Sized>(self, folder: &mut F) -> Self;
+    /// Rebuilds the node from its folded children, keeping its span.
+    fn fold_children<F: Fold + ?Sized>(self, folder: &mut F) -> Self;
+}
+
+/// Declares the visitor traits over every node type. The list is checked both ways at compile
+/// time: every generated `Visitable` impl calls its node's `visit_*` method, so a node missing
+/// from the list does not build, and every listed node must implement [`Visitable`].
+macro_rules! visit_traits {
+    ($($node:ident),* $(,)?) => {
+        const _: fn() = || {
+            fn assert_visitable<T: Visitable>() {}
+            $(assert_visitable::<$node>();)*
+        };
+
+        paste::paste! {
+            /// Immutable traversal of the owned AST, one method per node type.
+            ///
+            /// Every method recurses into the node's children by default, override the ones of
+            /// interest and call [`Visitable::walk`] to keep descending.
+            pub trait Visit {
+                /// Called with the span of every struct node before its children are visited.
+                fn visit_span(&mut self, _span: &Option<Span>) {}
+
+                $(
+                    fn [<visit_ $node:snake>](&mut self, node: &$node) {
+                        node.walk(self);
+                    }
+                )*
+            }
+
+            /// In place rewriting of the owned AST, one method per node type.
+            ///
+            /// Every method recurses into the node's children by default, call
+            /// [`Visitable::walk_mut`] from overrides to keep descending.
+            pub trait VisitMut {
+                /// Called with the span of every struct node before its children are visited.
+                fn visit_span_mut(&mut self, _span: &mut Option<Span>) {}
+
+                $(
+                    fn [<visit_ $node:snake _mut>](&mut self, node: &mut $node) {
+                        node.walk_mut(self);
+                    }
+                )*
+            }
+
+            /// Owned transformation of the owned AST, one method per node type.
+            ///
+            /// Every method rebuilds the node from its folded children by default, call
+            /// [`Visitable::fold_children`] from overrides to keep descending.
+            pub trait Fold {
+                $(
+                    fn [<fold_ $node:snake>](&mut self, node: $node) -> $node {
+                        node.fold_children(self)
+                    }
+                )*
+            }
+        }
+    };
+}
+
+visit_traits! {
+    Arg,
+    ArgClause,
+    AssociatedItemConstraint,
+    Attribute,
+    BinaryExpr,
+    BinaryOp,
+    BreakStatement,
+    Closure,
+    Condition,
+    ConstGenericParam,
+    Constant,
+    ContinueStatement,
+    ElseIfBlock,
+    Enum,
+    Expr,
+    ExprPath,
+    ExprStatement,
+    ExternFunction,
+    ExternType,
+    FixedSizeArray,
+    FixedSizeArrayType,
+    ForExpr,
+    FunctionCall,
+    FunctionDeclaration,
+    FunctionSignature,
+    FunctionWithBody,
+    GenericArg,
+    GenericArgNamed,
+    GenericParam,
+    Identifier,
+    IfExpr,
+    Impl,
+    ImplAlias,
+    ImplAnonymousGenericParam,
+    ImplItem,
+    ImplNamedGenericParam,
+    IndexExpr,
+    InlineMacroExpr,
+    InlineMacroItem,
+    Item,
+    LetCondition,
+    LetStatement,
+    LoopExpr,
+    MacroDeclarationItem,
+    MacroElement,
+    MacroParam,
+    MacroParamKind,
+    MacroRepetition,
+    MacroRepetitionOperator,
+    MacroRule,
+    MatchArm,
+    MatchExpr,
+    Member,
+    Modifier,
+    Module,
+    NamedArg,
+    Param,
+    ParamWithPatten,
+    PathSegment,
+    PathSegmentWithGenerics,
+    Pattern,
+    PatternEnum,
+    PatternStruct,
+    PatternStructParam,
+    ReturnStatement,
+    Statement,
+    Struct,
+    StructArg,
+    StructArgSingle,
+    StructConstructorCall,
+    TokenNode,
+    Trait,
+    TraitConstant,
+    TraitFunction,
+    TraitImpl,
+    TraitItem,
+    TraitType,
+    TupleType,
+    Type,
+    TypeAlias,
+    UnaryExpr,
+    UnaryOp,
+    UseItem,
+    UsePath,
+    UsePathLeaf,
+    UsePathSingle,
+    Variant,
+    Visibility,
+    WhileExpr,
+    WrappedMacro,
+}
+
+impl<T: Visitable> Visitable for Vec<T> {
+    fn accept<V: Visit + ?Sized>(&self, visitor: &mut V) {
+        self.iter().for_each(|node| node.accept(visitor));
+    }
+
+    fn walk<V: Visit + ?Sized>(&self, visitor: &mut V) {
+        self.accept(visitor);
+    }
+
+    fn accept_mut<V: VisitMut + ?Sized>(&mut self, visitor: &mut V) {
+        self.iter_mut().for_each(|node| node.accept_mut(visitor));
+    }
+
+    fn walk_mut<V: VisitMut + ?Sized>(&mut self, visitor: &mut V) {
+        self.accept_mut(visitor);
+    }
+
+    fn fold<F: Fold + ?Sized>(self, folder: &mut F) -> Self {
+        self.into_iter().map(|node| node.fold(folder)).collect()
+    }
+
+    fn fold_children<F: Fold + ?Sized>(self, folder: &mut F) -> Self {
+        self.fold(folder)
+    }
+}
+
+impl<T: Visitable> Visitable for Option<T> {
+    fn accept<V: Visit + ?Sized>(&self, visitor: &mut V) {
+        if let Some(node) = self {
+            node.accept(visitor);
+        }
+    }
+
+    fn walk<V: Visit + ?Sized>(&self, visitor: &mut V) {
+        self.accept(visitor);
+    }
+
+    fn accept_mut<V: VisitMut + ?Sized>(&mut self, visitor: &mut V) {
+        if let Some(node) = self {
+            node.accept_mut(visitor);
+        }
+    }
+
+    fn walk_mut<V: VisitMut + ?Sized>(&mut self, visitor: &mut V) {
+        self.accept_mut(visitor);
+    }
+
+    fn fold<F: Fold + ?Sized>(self, folder: &mut F) -> Self {
+        self.map(|node| node.fold(folder))
+    }
+
+    fn fold_children<F: Fold + ?Sized>(self, folder: &mut F) -> Self {
+        self.fold(folder)
+    }
+}
+
+impl<T: Visitable> Visitable for Box<T> {
+    fn accept<V: Visit + ?Sized>(&self, visitor: &mut V) {
+        (**self).accept(visitor);
+    }
+
+    fn walk<V: Visit + ?Sized>(&self, visitor: &mut V) {
+        (**self).walk(visitor);
+    }
+
+    fn accept_mut<V: VisitMut + ?Sized>(&mut self, visitor: &mut V) {
+        (**self).accept_mut(visitor);
+    }
+
+    fn walk_mut<V: VisitMut + ?Sized>(&mut self, visitor: &mut V) {
+        (**self).walk_mut(visitor);
+    }
+
+    fn fold<F: Fold + ?Sized>(self, folder: &mut F) -> Self {
+        Box::new((*self).fold(folder))
+    }
+
+    fn fold_children<F: Fold + ?Sized>(self, folder: &mut F) -> Self {
+        Box::new((*self).fold_children(folder))
+    }
+}
+
+macro_rules! leaf_visitable {
+    ($($ty:ty),*) => {
+        $(
+            impl Visitable for $ty {
+                fn accept<V: Visit + ?Sized>(&self, _visitor: &mut V) {}
+                fn walk<V: Visit + ?Sized>(&self, _visitor: &mut V) {}
+                fn accept_mut<V: VisitMut + ?Sized>(&mut self, _visitor: &mut V) {}
+                fn walk_mut<V: VisitMut + ?Sized>(&mut self, _visitor: &mut V) {}
+                fn fold<F: Fold + ?Sized>(self, _folder: &mut F) -> Self {
+                    self
+                }
+                fn fold_children<F: Fold + ?Sized>(self, _folder: &mut F) -> Self {
+                    self
+                }
+            }
+        )*
+    };
+}
+
+leaf_visitable!(String, bool);
+
+impl Visitable for Attribute {
+    fn accept<V: Visit + ?Sized>(&self, visitor: &mut V) {
+        visitor.visit_attribute(self);
+    }
+
+    fn walk<V: Visit + ?Sized>(&self, visitor: &mut V) {
+        visitor.visit_span(&self.span);
+        self.path().accept(visitor);
+        self.arguments.accept(visitor);
+    }
+
+    fn accept_mut<V: VisitMut + ?Sized>(&mut self, visitor: &mut V) {
+        visitor.visit_attribute_mut(self);
+    }
+
+    /// Visits the path in place, then recomputes [`Attribute::path_str`].
+    fn walk_mut<V: VisitMut + ?Sized>(&mut self, visitor: &mut V) {
+        visitor.visit_span_mut(&mut self.span);
+        self.path_mut().accept_mut(visitor);
+        self.sync_path_str();
+        self.arguments.accept_mut(visitor);
+    }
+
+    fn fold<F: Fold + ?Sized>(self, folder: &mut F) -> Self {
+        folder.fold_attribute(self)
+    }
+
+    fn fold_children<F: Fold + ?Sized>(mut self, folder: &mut F) -> Self {
+        let path = self.path().clone().fold(folder);
+        self.set_path(path);
+        self.arguments = self.arguments.fold(folder);
+        self
+    }
+}
+
+impl Visitable for IfExpr {
+    fn accept<V: Visit + ?Sized>(&self, visitor: &mut V) {
+        visitor.visit_if_expr(self);
+    }
+
+    fn walk<V: Visit + ?Sized>(&self, visitor: &mut V) {
+        visitor.visit_span(&self.span);
+        self.conditions.accept(visitor);
+        self.if_block.accept(visitor);
+        self.else_if_clauses.accept(visitor);
+        self.else_clause.accept(visitor);
+    }
+
+    fn accept_mut<V: VisitMut + ?Sized>(&mut self, visitor: &mut V) {
+        visitor.visit_if_expr_mut(self);
+    }
+
+    fn walk_mut<V: VisitMut + ?Sized>(&mut self, visitor: &mut V) {
+        visitor.visit_span_mut(&mut self.span);
+        self.conditions.accept_mut(visitor);
+        self.if_block.accept_mut(visitor);
+        self.else_if_clauses.accept_mut(visitor);
+        self.else_clause.accept_mut(visitor);
+    }
+
+    fn fold<F: Fold + ?Sized>(self, folder: &mut F) -> Self {
+        folder.fold_if_expr(self)
+    }
+
+    fn fold_children<F: Fold + ?Sized>(self, folder: &mut F) -> Self {
+        IfExpr {
+            conditions: self.conditions.fold(folder),
+            if_block: self.if_block.fold(folder),
+            else_if_clauses: self.else_if_clauses.fold(folder),
+            else_clause: self.else_clause.fold(folder),
+            span: self.span,
+        }
+    }
+}
+
+impl Visitable for Type {
+    fn accept<V: Visit + ?Sized>(&self, visitor: &mut V) {
+        visitor.visit_type(self);
+    }
+
+    fn walk<V: Visit + ?Sized>(&self, visitor: &mut V) {
+        match self {
+            Type::Path(path) => path.accept(visitor),
+            Type::Tuple(tuple) => tuple.accept(visitor),
+            Type::Snapshot(ty) | Type::Reference(ty) => ty.accept(visitor),
+            Type::Expr(expr) => expr.accept(visitor),
+            Type::FixedSizeArray(array) => array.accept(visitor),
+            Type::Unit | Type::Infer | Type::Never | Type::Missing => {}
+        }
+    }
+
+    fn accept_mut<V: VisitMut + ?Sized>(&mut self, visitor: &mut V) {
+        visitor.visit_type_mut(self);
+    }
+
+    fn walk_mut<V: VisitMut + ?Sized>(&mut self, visitor: &mut V) {
+        match self {
+            Type::Path(path) => path.accept_mut(visitor),
+            Type::Tuple(tuple) => tuple.accept_mut(visitor),
+            Type::Snapshot(ty) | Type::Reference(ty) => ty.accept_mut(visitor),
+            Type::Expr(expr) => expr.accept_mut(visitor),
+            Type::FixedSizeArray(array) => array.accept_mut(visitor),
+            Type::Unit | Type::Infer | Type::Never | Type::Missing => {}
+        }
+    }
+
+    fn fold<F: Fold + ?Sized>(self, folder: &mut F) -> Self {
+        folder.fold_type(self)
+    }
+
+    fn fold_children<F: Fold + ?Sized>(self, folder: &mut F) -> Self {
+        match self {
+            Type::Path(path) => Type::Path(path.fold(folder)),
+            Type::Tuple(tuple) => Type::Tuple(tuple.fold(folder)),
+            Type::Snapshot(ty) => Type::Snapshot(ty.fold(folder)),
+            Type::Reference(ty) => Type::Reference(ty.fold(folder)),
+            Type::Expr(expr) => Type::Expr(expr.fold(folder)),
+            Type::FixedSizeArray(array) => Type::FixedSizeArray(array.fold(folder)),
+            ty => ty,
+        }
+    }
+}
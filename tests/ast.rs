@@ -0,0 +1,52 @@
+use cairo_lang_parser::utils::SimpleParserDatabase;
+use cairo_lang_syntax::node::TypedSyntaxNode;
+use cairo_lang_syntax::node::ast::SyntaxFile;
+use cairo_syntax_parser::{
+    AstError, AstInto, AstTryInto, DocsTrait, Item, Span, item_from_token_stream,
+    str_to_token_stream,
+};
+
+#[test]
+fn try_from_ast_reports_missing_nodes() {
+    let db = SimpleParserDatabase::default();
+    let source = "fn f() {\n    let x = ;\n}\n";
+    let (node, _) = db.parse_virtual_with_diagnostics(source);
+    let file = SyntaxFile::from_syntax_node(&db, node);
+
+    let error = AstTryInto::<Vec<Item>>::ast_try_into(file.clone(), &db).unwrap_err();
+    assert_eq!(error.to_string(), "missing expression");
+    let offset = source.find(';').unwrap() as u32;
+    assert_eq!(
+        error,
+        AstError::Missing {
+            node: "expression",
+            span: Span::new(offset, offset),
+        }
+    );
+
+    // The lenient conversion keeps the node as `Missing`.
+    let items: Vec<Item> = file.ast_into(&db);
+    assert_eq!(items.len(), 1);
+}
+
+#[test]
+fn try_from_ast_matches_from_ast() {
+    let db = SimpleParserDatabase::default();
+    let node = db
+        .parse_virtual("#[derive(Drop)]\nstruct S<T> {\n    a: T,\n}\nfn f(x: u8) -> u8 {\n    if x > 1 { x } else { 0 }\n}\n")
+        .unwrap();
+    let file = SyntaxFile::from_syntax_node(&db, node);
+    let strict: Vec<Item> = AstTryInto::ast_try_into(file.clone(), &db).unwrap();
+    let lenient: Vec<Item> = file.ast_into(&db);
+    assert_eq!(strict, lenient);
+}
+
+#[test]
+fn doc_comments_after_attributes_are_kept() {
+    let code = "/// Before.\n#[derive(Drop)]\n/// After.\npub struct S {\n    #[key]\n    /// Key.\n    k: u8,\n}\n";
+    let Item::Struct(item) = item_from_token_stream(str_to_token_stream(code)).unwrap() else {
+        panic!("expected a struct");
+    };
+    assert_eq!(item.docs(), ["Before.", "After."]);
+    assert_eq!(item.members[0].docs(), ["Key."]);
+}
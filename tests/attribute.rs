@@ -0,0 +1,95 @@
+use cairo_syntax_parser::{
+    Attribute, AttributesTrait, CairoFormat, ExprPath, NamedArg, Struct, VisitMut, Visitable,
+    parse_attribute, parse_expr,
+};
+
+mod common;
+
+use common::structure;
+
+fn attributes(item: &Struct) -> String {
+    item.attributes.stringify()
+}
+
+#[test]
+fn constructs_attributes() {
+    let attribute = Attribute::new("starknet::interface");
+    assert_eq!(attribute.path_str(), "starknet::interface");
+    assert_eq!(
+        attribute,
+        parse_attribute("#[starknet::interface]").unwrap()
+    );
+
+    let attribute = Attribute::new("key").with_args([parse_expr("4").unwrap()]);
+    assert_eq!(attribute.stringify(), "#[key(4)]");
+
+    let attribute =
+        Attribute::new("key").with_args([NamedArg::new("size", parse_expr("4").unwrap())]);
+    assert_eq!(attribute, parse_attribute("#[key(size: 4)]").unwrap());
+
+    let derive = Attribute::derive(["Drop", "starknet::Store"]);
+    assert_eq!(
+        derive,
+        parse_attribute("#[derive(Drop, starknet::Store)]").unwrap()
+    );
+    assert_eq!(derive.get_derives(), ["Drop", "starknet::Store"]);
+}
+
+#[test]
+fn adds_and_removes_derives() {
+    let mut item = structure("#[derive(Drop)]\n#[derive(Serde)]\nstruct S {}");
+    item.add_derive("Serde");
+    item.add_derive("Copy");
+    assert_eq!(
+        attributes(&item),
+        "#[derive(Drop, Copy)]\n#[derive(Serde)]\n"
+    );
+    assert!(item.remove_derive("Serde"));
+    assert!(!item.remove_derive("Serde"));
+    assert_eq!(attributes(&item), "#[derive(Drop, Copy)]\n");
+
+    let mut item = structure("struct S {}");
+    item.add_derive("Drop");
+    item.add_derive(ExprPath::new(["starknet", "Store"]));
+    item.add_derive(ExprPath::from("Drop"));
+    assert_eq!(attributes(&item), "#[derive(Drop, starknet::Store)]\n");
+    assert_eq!(
+        Attribute::derive([ExprPath::new(["Serde"])]),
+        Attribute::derive(["Serde"])
+    );
+}
+
+#[test]
+fn removes_retains_and_sets_attributes() {
+    let mut item = structure("#[key]\n#[flat]\n#[key(size: 1)]\n#[doc]\nstruct S {}");
+    let removed = item.remove_attribute("key");
+    assert_eq!(removed.len(), 2);
+    assert_eq!(attributes(&item), "#[flat]\n#[doc]\n");
+    item.retain_attributes(|attr| attr.path_str() != "doc");
+    assert_eq!(attributes(&item), "#[flat]\n");
+
+    item.set_attribute_arg("key", "size", parse_expr("2").unwrap());
+    item.set_attribute_arg("key", "size", parse_expr("3").unwrap());
+    item.set_attribute_arg("key", "name", parse_expr("'k'").unwrap());
+    assert_eq!(attributes(&item), "#[flat]\n#[key(size: 3, name: 'k')]\n");
+}
+
+struct Rename;
+
+impl VisitMut for Rename {
+    fn visit_expr_path_mut(&mut self, path: &mut ExprPath) {
+        if path.last_ident() == Some("interface") {
+            *path = ExprPath::from("starknet::contract");
+        }
+    }
+}
+
+#[test]
+fn path_str_follows_the_path() {
+    let mut attribute = parse_attribute("#[starknet::interface]").unwrap();
+    attribute.accept_mut(&mut Rename);
+    assert_eq!(attribute.path_str(), "starknet::contract");
+    attribute.set_path("generate_trait");
+    assert_eq!(attribute.path_str(), "generate_trait");
+    assert_eq!(attribute.stringify(), "#[generate_trait]");
+}
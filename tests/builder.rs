@@ -0,0 +1,97 @@
+use cairo_syntax_parser::item::{FunctionWithBody, Impl};
+use cairo_syntax_parser::{
+    CairoFormat, Enum, ExprPath, Item, Parse, Span, Statement, Struct, Type, Variant, VisitMut,
+    Visitable, parse_expr, parse_type,
+};
+
+struct ClearSpans;
+
+impl VisitMut for ClearSpans {
+    fn visit_span_mut(&mut self, span: &mut Option<Span>) {
+        *span = None;
+    }
+}
+
+fn item(code: &str) -> Item {
+    let mut item = Item::parse(code).unwrap();
+    item.accept_mut(&mut ClearSpans);
+    item
+}
+
+fn ty(code: &str) -> Type {
+    parse_type(code).unwrap()
+}
+
+#[test]
+fn struct_builder_matches_parsed_struct() {
+    let built: Item = Struct::builder("Foo")
+        .doc("A point.")
+        .public()
+        .derive(["Drop"])
+        .derive([ExprPath::new(["starknet", "Store"])])
+        .type_param("T")
+        .member("x", ty("T"))
+        .member("y", ty("Array<felt252>"))
+        .build()
+        .into();
+    let parsed = item(
+        "/// A point.\n#[derive(Drop, starknet::Store)]\npub struct Foo<T> { x: T, y: Array<felt252> }",
+    );
+    assert_eq!(built.pretty(), parsed.pretty());
+    assert_eq!(built, parsed);
+}
+
+#[test]
+fn impl_builder_matches_parsed_impl() {
+    let function = FunctionWithBody::builder("get")
+        .ref_param("self", ty("ContractState"))
+        .param("key", ty("felt252"))
+        .returns(ty("u8"))
+        .statement(Statement::parse("self.touch();").unwrap())
+        .tail(parse_expr("1").unwrap())
+        .build();
+    let built: Item = Impl::builder("StoreImpl", "Store<ContractState>")
+        .function(function)
+        .build()
+        .into();
+    let parsed = item(
+        "impl StoreImpl of Store<ContractState> {\n    fn get(ref self: ContractState, key: felt252) -> u8 {\n        self.touch();\n        1\n    }\n}",
+    );
+    assert_eq!(built.pretty(), parsed.pretty());
+    assert_eq!(built, parsed);
+}
+
+#[test]
+fn enum_builder_matches_parsed_enum() {
+    let mut documented = Variant::new("Moved", Some(ty("(u32, u32)")));
+    documented.docs.push("The new position.".to_string());
+    let built: Item = Enum::builder("Event")
+        .doc("Contract events.")
+        .public()
+        .derive(["Drop", "Serde"])
+        .type_param("T")
+        .unit_variant("Started")
+        .variant("Value", ty("T"))
+        .variant_with(documented)
+        .build()
+        .into();
+    let parsed = item(
+        "/// Contract events.\n#[derive(Drop, Serde)]\npub enum Event<T> {\n    Started,\n    Value: T,\n    /// The new position.\n    Moved: (u32, u32),\n}",
+    );
+    assert_eq!(built.pretty(), parsed.pretty());
+    assert_eq!(built, parsed);
+}
+
+#[test]
+fn tail_stays_last() {
+    let built = FunctionWithBody::builder("f")
+        .returns(ty("u8"))
+        .tail(parse_expr("0").unwrap())
+        .statement(Statement::parse("let x = 1;").unwrap())
+        .tail(parse_expr("x").unwrap())
+        .statement(Statement::parse("touch();").unwrap())
+        .build();
+    let parsed = item("fn f() -> u8 {\n    let x = 1;\n    touch();\n    x\n}");
+    assert_eq!(Item::FreeFunction(built.clone()).pretty(), parsed.pretty());
+    assert_eq!(Item::FreeFunction(built), parsed);
+}
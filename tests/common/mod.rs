@@ -0,0 +1,8 @@
+use cairo_syntax_parser::{Item, Parse, Struct};
+
+pub fn structure(code: &str) -> Struct {
+    match Item::parse(code).unwrap() {
+        Item::Struct(item) => item,
+        item => panic!("expected a struct, found {item}"),
+    }
+}
@@ -0,0 +1,92 @@
+use cairo_syntax_parser::{Edit, Item, Member, Parse, Type, diff, parse_expr};
+
+fn items(code: &str) -> Vec<Item> {
+    Vec::<Item>::parse(code).unwrap()
+}
+
+fn paths(old: &str, new: &str) -> Vec<String> {
+    diff(&items(old), &items(new))
+        .edits
+        .iter()
+        .map(|edit| edit.path().join(" > "))
+        .collect()
+}
+
+#[test]
+fn identical_trees_have_no_edits() {
+    let code = "struct A { x: u8 }\nfn f() -> u8 { 1 }";
+    assert!(diff(&items(code), &items(&format!("\n\n{code}"))).is_empty());
+}
+
+#[test]
+fn edits_are_keyed_by_kind_and_name() {
+    let old = "struct A { x: u8, y: u8 }\nenum E { L, R }\nfn f() {}\nmod m { fn g() {} }";
+    let new = "struct A { x: u16, z: u8 }\nenum E { R, L }\nfn h() {}\nmod m { fn g() { 1; } }";
+    assert_eq!(
+        paths(old, new),
+        [
+            "Struct A > member x > type",
+            "Struct A > member y",
+            "Struct A > member z",
+            "Enum E > order",
+            "FunctionWithBody f",
+            "Module m > FunctionWithBody g > body",
+            "FunctionWithBody h",
+        ]
+    );
+}
+
+#[test]
+fn changes_render_as_code() {
+    let edits = diff(&items("struct A { x: u8 }"), &items("struct A { x: u16 }")).edits;
+    assert_eq!(
+        edits,
+        [Edit::Changed {
+            path: vec!["Struct A".into(), "member x".into(), "type".into()],
+            old: "u8".into(),
+            new: "u16".into(),
+        }]
+    );
+    let rendered = diff(&parse_expr("a + b").unwrap(), &parse_expr("a + c").unwrap()).to_string();
+    assert_eq!(rendered, "~ <root>\n    - a + b\n    + a + c\n");
+}
+
+#[test]
+fn single_nodes_compare_names() {
+    let item = |code: &str| Item::parse(code).unwrap();
+    let (Item::Struct(old), Item::Struct(new)) =
+        (item("struct A { x: u8 }"), item("struct B { x: u8 }"))
+    else {
+        panic!("expected structs");
+    };
+    assert_eq!(
+        diff(&old, &new).edits,
+        [Edit::Changed {
+            path: vec!["name".into()],
+            old: "A".into(),
+            new: "B".into(),
+        }]
+    );
+    assert_eq!(
+        diff(
+            &old.members[0],
+            &Member::new("y", Type::parse("u8").unwrap())
+        )
+        .edits,
+        [Edit::Changed {
+            path: vec!["name".into()],
+            old: "x".into(),
+            new: "y".into(),
+        }]
+    );
+    for (old, new) in [
+        ("enum E { L }", "enum F { L }"),
+        ("mod m {}", "mod n {}"),
+        ("trait T {}", "trait U {}"),
+        ("impl I of T {}", "impl J of T {}"),
+    ] {
+        let edits = diff(&item(old), &item(new)).edits;
+        let paths: Vec<_> = edits.iter().map(Edit::path).collect();
+        assert_eq!(paths, [["name"]], "{old}");
+    }
+}
@@ -0,0 +1,153 @@
+use core::num::traits::{Bounded, OverflowingAdd, Zero};
+use core::integer::u256_from_felt252;
+
+pub const TWO_POW_64: u128 = 0x10000000000000000;
+pub const MASK_8: u8 = 0xff;
+pub const BITS: [u8; 4] = [0b0001, 0b0010, 0b0100, 0b1000];
+const NEG_ONE: i8 = -1_i8;
+const OCTAL: felt252 = 0o777;
+
+pub type Felt = felt252;
+
+pub mod errors {
+    pub const DIVISION_BY_ZERO: felt252 = 'Division by zero';
+    pub const OVERFLOW: felt252 = 'Overflow';
+}
+
+#[derive(Copy, Drop, Debug, PartialEq)]
+pub struct Fraction {
+    pub numerator: u128,
+    pub denominator: NonZero<u128>,
+}
+
+extern fn u128_safe_divmod(lhs: u128, rhs: NonZero<u128>) -> (u128, u128) implicits(RangeCheck) nopanic;
+
+extern type Secp256Point;
+
+/// Returns `base` raised to `exp` using square and multiply.
+pub fn pow(base: u128, mut exp: u32) -> u128 {
+    let mut result = 1;
+    let mut base = base;
+    loop {
+        if exp == 0 {
+            break result;
+        }
+        if exp % 2 == 1 {
+            result = result * base;
+        }
+        base = base * base;
+        exp = exp / 2;
+    }
+}
+
+pub fn gcd(mut a: u128, mut b: u128) -> u128 {
+    while b != 0 {
+        let r = a % b;
+        a = b;
+        b = r;
+    };
+    a
+}
+
+pub fn checked_add(a: u64, b: u64) -> Option<u64> {
+    let (sum, overflow) = a.overflowing_add(b);
+    if overflow {
+        Option::None
+    } else {
+        Option::Some(sum)
+    }
+}
+
+fn clamp<T, +PartialOrd<T>, +Copy<T>, +Drop<T>>(value: T, min: T, max: T) -> T {
+    if value < min {
+        min
+    } else if value > max {
+        max
+    } else {
+        value
+    }
+}
+
+fn bit_tricks(x: u32, y: u32) -> (u32, u32, u32, bool) {
+    let and = x & y;
+    let or = x | y;
+    let xor = (x ^ y) & ~0_u32;
+    let is_power = x != 0 && (x & (x - 1)) == 0;
+    (and, or, xor, is_power || y >= 2 && !(y <= 10))
+}
+
+fn closures() -> u32 {
+    let double = |x: u32| x * 2;
+    let add = |a: u32, b: u32| -> u32 {
+        a + b
+    };
+    let values = array![1, 2, 3];
+    let offset = 10;
+    let shifted: Array<u32> = values.span().into_iter().map(|x| *x + offset).collect();
+    add(double(shifted[0]), *shifted.at(1))
+}
+
+fn ranges() -> u32 {
+    let mut total = 0;
+    for i in 0..10_u32 {
+        total += i;
+    }
+    for i in 0..=3_u32 {
+        total -= i;
+    }
+    total
+}
+
+fn fixed_arrays() -> felt252 {
+    let arr: [felt252; 3] = [1, 2, 3];
+    let [a, b, c] = arr;
+    let nested = [[0_u8; 2]; 2];
+    let span = arr.span();
+    a + b + c + (*span[0]) + nested.len().into()
+}
+
+fn tuples_and_snapshots(pair: @(u32, u32)) -> u32 {
+    let (a, b) = *pair;
+    let ((x, _), y) = ((a, b), @a);
+    let _unit = ();
+    x + *y
+}
+
+fn literals() -> (felt252, ByteArray, u256, i64) {
+    let short = 'hello\n';
+    let long: ByteArray = "a long string with \"escapes\" and 'quotes'";
+    let big: u256 = 0x1234_5678_u256;
+    (short, long, big, -42_i64)
+}
+
+fn let_else(value: Option<u32>) -> u32 {
+    let Option::Some(inner) = value else {
+        return 0;
+    };
+    let mut counter = inner;
+    counter *= 2;
+    counter /= 3;
+    counter %= 7;
+    counter -= 1;
+    counter
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{gcd, pow};
+
+    #[test]
+    #[available_gas(100000)]
+    fn test_pow() {
+        assert!(pow(2, 10) == 1024, "2^10 should be {}", 1024);
+        assert_eq!(gcd(12, 18), 6);
+    }
+
+    #[test]
+    #[should_panic(expected: ('Overflow',))]
+    fn test_overflow() {
+        let max: u128 = Bounded::MAX;
+        let _ = max + 1;
+        panic!("unreachable");
+    }
+}
@@ -0,0 +1,121 @@
+//! Optional values, modelled on `core::option`.
+
+use crate::array::{ArrayTrait, SpanTrait};
+use crate::traits::{Default, Destruct, Into};
+use super::*;
+
+/// A value that may be absent.
+#[derive(Copy, Drop, Debug, Serde, PartialEq)]
+pub enum Option<T> {
+    Some: T,
+    None,
+}
+
+#[derive(Drop)]
+/// Documentation written between the attributes and the item.
+struct OptionPair<T> {
+    #[key]
+    /// The first value.
+    first: Option<T>,
+    second: Option<T>,
+}
+
+pub trait OptionTrait<T> {
+    /// Returns the contained `Some` value, panicking with `err` otherwise.
+    fn expect(self: Option<T>, err: felt252) -> T;
+    fn unwrap(self: Option<T>) -> T;
+    fn ok_or<E, +Destruct<E>>(self: Option<T>, err: E) -> Result<T, E>;
+    #[must_use]
+    fn is_some(self: @Option<T>) -> bool;
+    fn is_none(self: @Option<T>) -> bool;
+    fn unwrap_or<+Destruct<T>>(self: Option<T>, default: T) -> T;
+    fn unwrap_or_default<+Default<T>>(self: Option<T>) -> T;
+    fn map<U, F, +Destruct<F>, +core::ops::FnOnce<F, (T,)>[Output: U]>(
+        self: Option<T>, f: F,
+    ) -> Option<U>;
+}
+
+pub impl OptionTraitImpl<T> of OptionTrait<T> {
+    #[inline(always)]
+    fn expect(self: Option<T>, err: felt252) -> T {
+        match self {
+            Option::Some(x) => x,
+            Option::None => crate::panic_with_felt252(err),
+        }
+    }
+
+    #[inline(always)]
+    fn unwrap(self: Option<T>) -> T {
+        self.expect('Option::unwrap failed.')
+    }
+
+    fn ok_or<E, +Destruct<E>>(self: Option<T>, err: E) -> Result<T, E> {
+        match self {
+            Option::Some(v) => Result::Ok(v),
+            Option::None => Result::Err(err),
+        }
+    }
+
+    #[inline]
+    fn is_some(self: @Option<T>) -> bool {
+        match self {
+            Option::Some(_) => true,
+            Option::None => false,
+        }
+    }
+
+    #[inline]
+    fn is_none(self: @Option<T>) -> bool {
+        !self.is_some()
+    }
+
+    fn unwrap_or<+Destruct<T>>(self: Option<T>, default: T) -> T {
+        match self {
+            Option::Some(x) => x,
+            Option::None => default,
+        }
+    }
+
+    fn unwrap_or_default<+Default<T>>(self: Option<T>) -> T {
+        if let Option::Some(x) = self {
+            x
+        } else {
+            Default::default()
+        }
+    }
+
+    fn map<U, F, +Destruct<F>, +core::ops::FnOnce<F, (T,)>[Output: U]>(
+        self: Option<T>, f: F,
+    ) -> Option<U> {
+        match self {
+            Option::Some(x) => Option::Some(f(x)),
+            Option::None => Option::None,
+        }
+    }
+}
+
+impl OptionDefault<T> of Default<Option<T>> {
+    fn default() -> Option<T> {
+        Option::None
+    }
+}
+
+/// Sums the values that are present.
+pub fn sum_present(values: Span<Option<u32>>) -> u32 {
+    let mut total = 0;
+    for value in values {
+        if let Option::Some(v) = value {
+            total += *v;
+        }
+    }
+    total
+}
+
+pub fn first_some<T, +Copy<T>, +Drop<T>>(mut values: Span<Option<T>>) -> Option<T> {
+    while let Option::Some(value) = values.pop_front() {
+        if value.is_some() {
+            return *value;
+        }
+    }
+    Option::None
+}
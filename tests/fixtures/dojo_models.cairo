@@ -0,0 +1,159 @@
+use starknet::{ContractAddress, get_caller_address};
+
+#[derive(Serde, Copy, Drop, Introspect, PartialEq, Debug, Default)]
+pub enum Direction {
+    #[default]
+    None,
+    Left,
+    Right,
+    Up,
+    Down,
+}
+
+#[derive(Copy, Drop, Serde, Debug, IntrospectPacked)]
+pub struct Vec2 {
+    pub x: u32,
+    pub y: u32,
+}
+
+#[derive(Copy, Drop, Serde, Debug)]
+#[dojo::model]
+pub struct Position {
+    #[key]
+    pub player: ContractAddress,
+    pub vec: Vec2,
+}
+
+#[derive(Drop, Serde, Debug)]
+#[dojo::model(namespace: "dojo_starter", nomapping: true)]
+pub struct Moves {
+    #[key]
+    pub player: ContractAddress,
+    pub remaining: u8,
+    pub last_direction: Option<Direction>,
+    pub can_move: bool,
+    pub history: Array<Direction>,
+}
+
+#[derive(Drop, Serde)]
+#[dojo::event]
+pub struct Moved {
+    #[key]
+    pub player: ContractAddress,
+    pub direction: Direction,
+}
+
+impl DirectionIntoFelt252 of Into<Direction, felt252> {
+    fn into(self: Direction) -> felt252 {
+        match self {
+            Direction::None => 0,
+            Direction::Left => 1,
+            Direction::Right => 2,
+            Direction::Up => 3,
+            Direction::Down => 4,
+        }
+    }
+}
+
+#[generate_trait]
+impl Vec2Impl of Vec2Trait {
+    fn is_zero(self: Vec2) -> bool {
+        if self.x - self.y == 0 {
+            return true;
+        }
+        false
+    }
+
+    fn is_equal(self: Vec2, b: Vec2) -> bool {
+        self.x == b.x && self.y == b.y
+    }
+}
+
+#[starknet::interface]
+pub trait IActions<T> {
+    fn spawn(ref self: T);
+    fn move(ref self: T, direction: Direction);
+}
+
+#[dojo::contract]
+pub mod actions {
+    use dojo::event::EventStorage;
+    use dojo::model::{ModelStorage, ModelValueStorage};
+    use super::{Direction, IActions, Moved, Moves, Position, Vec2, next_position};
+    use starknet::{ContractAddress, get_caller_address};
+
+    #[abi(embed_v0)]
+    impl ActionsImpl of IActions<ContractState> {
+        fn spawn(ref self: ContractState) {
+            let mut world = self.world_default();
+            let player = get_caller_address();
+            let position: Position = world.read_model(player);
+            let new_position = Position {
+                player, vec: Vec2 { x: position.vec.x + 10, y: position.vec.y + 10 },
+            };
+            world.write_model(@new_position);
+            let moves = Moves {
+                player, remaining: 100, last_direction: Option::None, can_move: true, history: array![],
+            };
+            world.write_model(@moves);
+        }
+
+        fn move(ref self: ContractState, direction: Direction) {
+            let mut world = self.world_default();
+            let player = get_caller_address();
+            let position: Position = world.read_model(player);
+            let mut moves: Moves = world.read_model(player);
+            if !moves.can_move {
+                return;
+            }
+            moves.remaining -= 1;
+            moves.last_direction = Option::Some(direction);
+            moves.history.append(direction);
+            let next = next_position(position, moves.last_direction);
+            world.write_model(@next);
+            world.write_model(@moves);
+            world.emit_event(@Moved { player, direction });
+        }
+    }
+
+    #[generate_trait]
+    impl InternalImpl of InternalTrait {
+        fn world_default(self: @ContractState) -> dojo::world::WorldStorage {
+            self.world(@"dojo_starter")
+        }
+    }
+}
+
+fn next_position(mut position: Position, direction: Option<Direction>) -> Position {
+    match direction {
+        Option::None => { return position; },
+        Option::Some(d) => match d {
+            Direction::None => {},
+            Direction::Left => { position.vec.x -= 1; },
+            Direction::Right => { position.vec.x += 1; },
+            Direction::Up => { position.vec.y -= 1; },
+            Direction::Down => { position.vec.y += 1; },
+        },
+    };
+    position
+}
+
+fn with_defaults() -> Moves {
+    let base = Moves {
+        player: get_caller_address(),
+        remaining: 0,
+        last_direction: Option::None,
+        can_move: false,
+        history: ArrayTrait::new(),
+    };
+    Moves { remaining: 99, ..base }
+}
+
+fn direction_name(direction: @Direction) -> ByteArray {
+    let name = match direction {
+        Direction::None => "none",
+        Direction::Left | Direction::Right => "horizontal",
+        Direction::Up | Direction::Down => "vertical",
+    };
+    format!("direction: {}", name)
+}
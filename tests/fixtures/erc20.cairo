@@ -0,0 +1,241 @@
+use starknet::ContractAddress;
+
+#[starknet::interface]
+pub trait IERC20<TState> {
+    fn total_supply(self: @TState) -> u256;
+    fn balance_of(self: @TState, account: ContractAddress) -> u256;
+    fn allowance(self: @TState, owner: ContractAddress, spender: ContractAddress) -> u256;
+    fn transfer(ref self: TState, recipient: ContractAddress, amount: u256) -> bool;
+    fn transfer_from(
+        ref self: TState, sender: ContractAddress, recipient: ContractAddress, amount: u256,
+    ) -> bool;
+    fn approve(ref self: TState, spender: ContractAddress, amount: u256) -> bool;
+}
+
+/// ERC20 token following the OpenZeppelin component layout.
+#[starknet::component]
+pub mod ERC20Component {
+    use core::num::traits::Bounded;
+    use core::num::traits::Zero;
+    use starknet::storage::{
+        Map, StorageMapReadAccess, StorageMapWriteAccess, StoragePointerReadAccess,
+        StoragePointerWriteAccess,
+    };
+    use starknet::{ContractAddress, get_caller_address};
+    use super::IERC20;
+
+    #[storage]
+    pub struct Storage {
+        pub ERC20_name: ByteArray,
+        pub ERC20_symbol: ByteArray,
+        pub ERC20_total_supply: u256,
+        pub ERC20_balances: Map<ContractAddress, u256>,
+        pub ERC20_allowances: Map<(ContractAddress, ContractAddress), u256>,
+    }
+
+    #[event]
+    #[derive(Drop, PartialEq, starknet::Event)]
+    pub enum Event {
+        Transfer: Transfer,
+        Approval: Approval,
+    }
+
+    #[derive(Drop, PartialEq, starknet::Event)]
+    pub struct Transfer {
+        #[key]
+        pub from: ContractAddress,
+        #[key]
+        pub to: ContractAddress,
+        pub value: u256,
+    }
+
+    #[derive(Drop, PartialEq, starknet::Event)]
+    pub struct Approval {
+        #[key]
+        pub owner: ContractAddress,
+        #[key]
+        pub spender: ContractAddress,
+        pub value: u256,
+    }
+
+    pub mod Errors {
+        pub const APPROVE_FROM_ZERO: felt252 = 'ERC20: approve from 0';
+        pub const APPROVE_TO_ZERO: felt252 = 'ERC20: approve to 0';
+        pub const TRANSFER_FROM_ZERO: felt252 = 'ERC20: transfer from 0';
+        pub const TRANSFER_TO_ZERO: felt252 = 'ERC20: transfer to 0';
+        pub const INSUFFICIENT_BALANCE: felt252 = 'ERC20: insufficient balance';
+        pub const INSUFFICIENT_ALLOWANCE: felt252 = 'ERC20: insufficient allowance';
+    }
+
+    pub trait ERC20HooksTrait<TContractState> {
+        fn before_update(
+            ref self: ComponentState<TContractState>,
+            from: ContractAddress,
+            recipient: ContractAddress,
+            amount: u256,
+        ) {}
+    }
+
+    #[embeddable_as(ERC20Impl)]
+    impl ERC20<
+        TContractState, +HasComponent<TContractState>, +ERC20HooksTrait<TContractState>,
+    > of IERC20<ComponentState<TContractState>> {
+        fn total_supply(self: @ComponentState<TContractState>) -> u256 {
+            self.ERC20_total_supply.read()
+        }
+
+        fn balance_of(self: @ComponentState<TContractState>, account: ContractAddress) -> u256 {
+            self.ERC20_balances.read(account)
+        }
+
+        fn allowance(
+            self: @ComponentState<TContractState>, owner: ContractAddress, spender: ContractAddress,
+        ) -> u256 {
+            self.ERC20_allowances.read((owner, spender))
+        }
+
+        fn transfer(
+            ref self: ComponentState<TContractState>, recipient: ContractAddress, amount: u256,
+        ) -> bool {
+            let sender = get_caller_address();
+            self._transfer(sender, recipient, amount);
+            true
+        }
+
+        fn transfer_from(
+            ref self: ComponentState<TContractState>,
+            sender: ContractAddress,
+            recipient: ContractAddress,
+            amount: u256,
+        ) -> bool {
+            let caller = get_caller_address();
+            self._spend_allowance(sender, caller, amount);
+            self._transfer(sender, recipient, amount);
+            true
+        }
+
+        fn approve(
+            ref self: ComponentState<TContractState>, spender: ContractAddress, amount: u256,
+        ) -> bool {
+            let caller = get_caller_address();
+            self._approve(caller, spender, amount);
+            true
+        }
+    }
+
+    #[generate_trait]
+    pub impl InternalImpl<
+        TContractState, +HasComponent<TContractState>, impl Hooks: ERC20HooksTrait<TContractState>,
+    > of InternalTrait<TContractState> {
+        fn initializer(
+            ref self: ComponentState<TContractState>, name: ByteArray, symbol: ByteArray,
+        ) {
+            self.ERC20_name.write(name);
+            self.ERC20_symbol.write(symbol);
+        }
+
+        fn mint(ref self: ComponentState<TContractState>, recipient: ContractAddress, amount: u256) {
+            assert(!recipient.is_zero(), Errors::TRANSFER_TO_ZERO);
+            self.update(Zero::zero(), recipient, amount);
+        }
+
+        fn update(
+            ref self: ComponentState<TContractState>,
+            from: ContractAddress,
+            to: ContractAddress,
+            amount: u256,
+        ) {
+            Hooks::before_update(ref self, from, to, amount);
+            let zero_address = Zero::zero();
+            if from == zero_address {
+                let total_supply = self.ERC20_total_supply.read();
+                self.ERC20_total_supply.write(total_supply + amount);
+            } else {
+                let from_balance = self.ERC20_balances.read(from);
+                assert(from_balance >= amount, Errors::INSUFFICIENT_BALANCE);
+                self.ERC20_balances.write(from, from_balance - amount);
+            }
+            if to == zero_address {
+                let total_supply = self.ERC20_total_supply.read();
+                self.ERC20_total_supply.write(total_supply - amount);
+            } else {
+                let to_balance = self.ERC20_balances.read(to);
+                self.ERC20_balances.write(to, to_balance + amount);
+            }
+            self.emit(Transfer { from, to, value: amount });
+        }
+
+        fn _transfer(
+            ref self: ComponentState<TContractState>,
+            sender: ContractAddress,
+            recipient: ContractAddress,
+            amount: u256,
+        ) {
+            assert(!sender.is_zero(), Errors::TRANSFER_FROM_ZERO);
+            assert(!recipient.is_zero(), Errors::TRANSFER_TO_ZERO);
+            self.update(sender, recipient, amount);
+        }
+
+        fn _approve(
+            ref self: ComponentState<TContractState>,
+            owner: ContractAddress,
+            spender: ContractAddress,
+            amount: u256,
+        ) {
+            assert(!owner.is_zero(), Errors::APPROVE_FROM_ZERO);
+            assert(!spender.is_zero(), Errors::APPROVE_TO_ZERO);
+            self.ERC20_allowances.write((owner, spender), amount);
+            self.emit(Approval { owner, spender, value: amount });
+        }
+
+        fn _spend_allowance(
+            ref self: ComponentState<TContractState>,
+            owner: ContractAddress,
+            spender: ContractAddress,
+            amount: u256,
+        ) {
+            let current_allowance = self.ERC20_allowances.read((owner, spender));
+            if current_allowance != Bounded::MAX {
+                assert(current_allowance >= amount, Errors::INSUFFICIENT_ALLOWANCE);
+                self._approve(owner, spender, current_allowance - amount);
+            }
+        }
+    }
+}
+
+pub impl ERC20HooksEmptyImpl<
+    TContractState,
+> of ERC20Component::ERC20HooksTrait<TContractState> {}
+
+#[starknet::contract]
+mod MyToken {
+    use starknet::ContractAddress;
+    use super::{ERC20Component, ERC20HooksEmptyImpl};
+
+    component!(path: ERC20Component, storage: erc20, event: ERC20Event);
+
+    #[abi(embed_v0)]
+    impl ERC20Impl = ERC20Component::ERC20Impl<ContractState>;
+    impl ERC20InternalImpl = ERC20Component::InternalImpl<ContractState>;
+
+    #[storage]
+    struct Storage {
+        #[substorage(v0)]
+        erc20: ERC20Component::Storage,
+    }
+
+    #[event]
+    #[derive(Drop, starknet::Event)]
+    enum Event {
+        #[flat]
+        ERC20Event: ERC20Component::Event,
+    }
+
+    #[constructor]
+    fn constructor(ref self: ContractState, initial_supply: u256, recipient: ContractAddress) {
+        let name = "MyToken";
+        let symbol = "MTK";
+        self.erc20.initializer(name, symbol);
+        self.erc20.mint(recipient, initial_supply);
+    }
+}
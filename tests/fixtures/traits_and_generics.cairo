@@ -0,0 +1,95 @@
+use core::hash::{Hash, HashStateTrait};
+use core::poseidon::PoseidonTrait as Poseidon;
+
+pub trait Shape<T> {
+    type Unit;
+    const SIDES: u32;
+    fn area(self: @T) -> u64;
+    fn name(self: @T) -> ByteArray {
+        "shape"
+    }
+    impl Hasher: Hash<T, core::poseidon::HashState>;
+}
+
+#[derive(Copy, Drop)]
+pub(crate) struct Square {
+    side: u32,
+}
+
+impl SquareShape of Shape<Square> {
+    type Unit = u32;
+    const SIDES: u32 = 4;
+
+    fn area(self: @Square) -> u64 {
+        let side: u64 = (*self.side).into();
+        side * side
+    }
+}
+
+pub struct Wrapper<T, const N: usize, impl TDrop: Drop<T>> {
+    values: [T; N],
+}
+
+impl WrapperDrop<T, const N: usize, +Drop<T>, -Copy<T>> of Drop<Wrapper<T, N>>;
+
+pub impl SquarePartialEq = core::traits::PartialEq<Square>;
+
+fn total_area<T, impl TShape: Shape<T>, +Drop<T>>(shapes: Span<T>) -> u64 {
+    let mut total = 0;
+    for shape in shapes {
+        total += TShape::area(shape);
+    }
+    total
+}
+
+fn hash_pair(a: felt252, b: felt252) -> felt252 {
+    Poseidon::new().update(a).update(b).finalize()
+}
+
+fn generic_calls() -> Array<u8> {
+    let mut values = ArrayTrait::<u8>::new();
+    values.append(1_u8);
+    let converted: u16 = core::traits::Into::<u8, u16>::into(*values[0]);
+    let _ = Option::<u16>::Some(converted);
+    values
+}
+
+fn nested_control(x: u32) -> u32 {
+    let value = if x > 10 {
+        match x % 3 {
+            0 => 1,
+            1 | 2 => {
+                let y = x / 2;
+                y + 1
+            },
+            _ => 0,
+        }
+    } else if x == 0 {
+        loop {
+            break 5;
+        }
+    } else {
+        x
+    };
+    value
+}
+
+fn errors(value: Result<u32, felt252>) -> Result<u32, felt252> {
+    let inner = value?;
+    let doubled = inner.checked_mul(2).ok_or('overflow')?;
+    Result::Ok(doubled)
+}
+
+macro add_one {
+    ($x:expr) => {
+        $x + 1
+    };
+}
+
+fn use_macro() -> u32 {
+    add_one!(41)
+}
+
+mod empty_module {}
+
+mod declared_elsewhere;
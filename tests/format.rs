@@ -0,0 +1,85 @@
+use cairo_syntax_parser::{
+    CairoFormat, FormatConfig, items_from_token_stream, str_to_token_stream,
+};
+
+const CODE: &str = "struct Point { x: u32, y: u32 }\nfn f(first: felt252, second: felt252) -> felt252 {\n    call(first, second, Point { x: 1, y: 2 })\n}\n";
+
+fn pretty(config: &FormatConfig) -> String {
+    items_from_token_stream(str_to_token_stream(CODE))
+        .unwrap()
+        .pretty_with(config)
+}
+
+#[test]
+fn wraps_lists_past_max_width() {
+    let printed = pretty(&FormatConfig::default().with_max_width(Some(40)));
+    assert_eq!(
+        printed,
+        "struct Point {\n    x: u32,\n    y: u32,\n}\n\nfn f(\n    first: felt252,\n    second: felt252,\n) -> felt252 {\n    call(\n        first,\n        second,\n        Point { x: 1, y: 2 },\n    )\n}\n"
+    );
+}
+
+#[test]
+fn compact_never_wraps() {
+    let printed = pretty(&FormatConfig::compact().with_max_width(None));
+    assert!(printed.contains("fn f(first: felt252, second: felt252) -> felt252 {"));
+    assert!(printed.contains("    call(first, second, Point { x: 1, y: 2 })"));
+}
+
+#[test]
+fn indent_width_and_trailing_commas() {
+    let config = FormatConfig::default()
+        .with_indent_width(2)
+        .with_max_width(Some(30))
+        .with_trailing_commas(false);
+    let printed = pretty(&config);
+    assert!(printed.starts_with("struct Point {\n  x: u32,\n  y: u32\n}\n"));
+    assert!(printed.contains("fn f(\n  first: felt252,\n  second: felt252\n) -> felt252 {\n"));
+}
+
+fn pretty_code(code: &str) -> String {
+    items_from_token_stream(str_to_token_stream(code))
+        .unwrap()
+        .pretty()
+}
+
+#[test]
+fn indents_nested_blocks() {
+    let printed = pretty_code(
+        "mod m {\n/// Docs\n#[inline] pub(crate) fn f() { match x { A => { 1 }, B => 2, } } }",
+    );
+    assert_eq!(
+        printed,
+        "mod m {\n    /// Docs\n    #[inline]\n    pub(crate) fn f() {\n        match x {\n            A => {\n                1\n            },\n            B => 2,\n        }\n    }\n}\n"
+    );
+}
+
+#[test]
+fn separates_items_with_bodies() {
+    let printed = pretty_code(
+        "const A: u8 = 1; const B: u8 = 2; trait T { fn a(); fn b() {} fn c(); } fn g() {}",
+    );
+    assert_eq!(
+        printed,
+        "const A: u8 = 1;\nconst B: u8 = 2;\ntrait T {\n    fn a();\n    fn b() {}\n\n    fn c();\n}\n\nfn g() {}\n"
+    );
+}
+
+#[test]
+fn keeps_literal_and_macro_text() {
+    let printed = pretty_code("fn f() { let s = \"a\n  b\"; } macro m { ($x:expr) => { {$x} }; }");
+    assert_eq!(
+        printed,
+        "fn f() {\n    let s = \"a\n  b\";\n}\n\nmacro m {\n    ($x:expr) => {{$x}};\n}\n"
+    );
+}
+
+#[test]
+fn formats_deeply_nested_groups() {
+    let depth = 64;
+    let code = format!("fn f() {{ {}x{} }}", "g(".repeat(depth), ")".repeat(depth));
+    let items = items_from_token_stream(str_to_token_stream(&code)).unwrap();
+    assert!(items.stringify().contains(&code[9..code.len() - 2]));
+    let printed = items.pretty();
+    assert_eq!(printed.matches("g(").count(), depth);
+}
@@ -0,0 +1,123 @@
+use cairo_syntax_parser::{
+    AttributeError, AttributeErrorKind, AttributesTrait, CairoFormat, FromAttribute, Item, Parse,
+    Span, Type, from_attribute, parse_attribute,
+};
+
+from_attribute! {
+    #[derive(Debug, PartialEq)]
+    struct KeyArgs {
+        name: Option<String>,
+        size: u32,
+        packed: bool,
+        ty: Option<Type>,
+    }
+}
+
+fn key_args(code: &str) -> Result<KeyArgs, AttributeError> {
+    KeyArgs::from_attribute(&parse_attribute(code).unwrap())
+}
+
+fn error_kind(code: &str) -> AttributeErrorKind {
+    key_args(code).unwrap_err().kind
+}
+
+#[test]
+fn reads_named_and_flag_arguments() {
+    let args = key_args("#[key(size: 4, name: \"owner\", packed, ty: u256)]").unwrap();
+    assert_eq!(args.name.as_deref(), Some("owner"));
+    assert_eq!(args.size, 4);
+    assert!(args.packed);
+    assert_eq!(args.ty.unwrap().stringify(), "u256");
+
+    let args = key_args("#[key(size = 0x10)]").unwrap();
+    assert_eq!((args.name, args.size, args.packed), (None, 16, false));
+}
+
+#[test]
+fn reports_unknown_missing_duplicate_and_invalid_arguments() {
+    assert_eq!(
+        error_kind("#[key(size: 1, offset: 2)]"),
+        AttributeErrorKind::Unknown("offset".to_string())
+    );
+    assert_eq!(
+        error_kind("#[key(packed)]"),
+        AttributeErrorKind::Missing("size".to_string())
+    );
+    assert_eq!(
+        error_kind("#[key(size: 1, size: 2)]"),
+        AttributeErrorKind::Duplicate("size".to_string())
+    );
+    assert_eq!(
+        error_kind("#[key(size: 300, packed: 1)]"),
+        AttributeErrorKind::Invalid {
+            name: "packed".to_string(),
+            reason: "expected `true` or `false`, found `1`".to_string(),
+        }
+    );
+    assert!(matches!(
+        error_kind("#[key(size: -1)]"),
+        AttributeErrorKind::Invalid { name, .. } if name == "size"
+    ));
+    assert!(matches!(
+        error_kind("#[key(1 + 2)]"),
+        AttributeErrorKind::Unexpected(_)
+    ));
+}
+
+#[test]
+fn errors_point_at_the_argument() {
+    let code = "#[key(size: 1, offset: 2)]\nstruct S {}";
+    let item = Item::parse(code).unwrap();
+    let Item::Struct(item) = item else {
+        panic!("expected a struct")
+    };
+    let error = item
+        .get_attribute_args::<KeyArgs>("key")
+        .unwrap()
+        .unwrap_err();
+    let Span { start, end } = error.span.unwrap();
+    assert_eq!(&code[start as usize..end as usize], "offset: 2");
+    assert_eq!(error.to_string(), "#[key]: unknown argument `offset`");
+    assert!(item.get_attribute_args::<KeyArgs>("value").is_none());
+}
+
+from_attribute! {
+    struct RawArgs {
+        r#override: bool,
+        r#move: Option<u8>,
+    }
+}
+
+#[test]
+fn raw_field_names_read_plain_arguments() {
+    let args =
+        RawArgs::from_attribute(&parse_attribute("#[raw(override, move: 2)]").unwrap()).unwrap();
+    assert!(args.r#override);
+    assert_eq!(args.r#move, Some(2));
+    let args = RawArgs::from_attribute(&parse_attribute("#[raw]").unwrap()).unwrap();
+    assert_eq!((args.r#override, args.r#move), (false, None));
+}
+
+from_attribute! {
+    #[derive(Debug)]
+    pub struct RenamedArgs {
+        /// Read from `ty`.
+        #[arg(rename = "ty")]
+        pub kind: Option<Type>,
+        #[allow(dead_code)]
+        #[arg(rename = "size")]
+        len: u32,
+    }
+}
+
+#[test]
+fn renamed_fields_read_the_given_argument() {
+    let args = RenamedArgs::from_attribute(&parse_attribute("#[store(ty: u8, size: 2)]").unwrap())
+        .unwrap();
+    assert_eq!(args.kind.unwrap().stringify(), "u8");
+    assert_eq!(args.len, 2);
+    let error =
+        RenamedArgs::from_attribute(&parse_attribute("#[store(kind: u8, size: 2)]").unwrap())
+            .unwrap_err();
+    assert_eq!(error.kind, AttributeErrorKind::Unknown("kind".to_string()));
+}
@@ -0,0 +1,67 @@
+use cairo_syntax_parser::{ExprPath, GenericParam, GenericParamsTrait, Parse, parse_type};
+
+mod common;
+
+use common::structure;
+
+#[test]
+fn declaration_and_usage_forms() {
+    let item = structure("struct S<T, const N: usize, +Drop<T>, impl H: Hash<T>> {}");
+    assert_eq!(item.all_generic_params().len(), 4);
+    assert_eq!(
+        item.generic_declaration_string(),
+        "<T, const N: usize, +Drop<T>, impl H: Hash<T>>"
+    );
+    assert_eq!(item.generic_types_string(), "<T, N>");
+    let mut call = String::new();
+    item.cwrite_generic_types_call(&mut call).unwrap();
+    assert_eq!(call, "::<T, N>");
+
+    let item = structure("struct S {}");
+    assert!(item.all_generic_params().is_empty());
+    assert_eq!(item.generic_declaration_string(), "");
+    assert_eq!(item.generic_types_string(), "");
+}
+
+#[test]
+fn adds_bounds_for_each_type_param() {
+    let item = structure("struct S<T, U, const N: usize, +Drop<T>> {}");
+    let params = item.generic_params_with_bounds(&["Drop", "Serde"]);
+    assert_eq!(
+        params.generic_declaration_string(),
+        "<T, U, const N: usize, +Drop<T>, +Serde<T>, +Drop<U>, +Serde<U>>"
+    );
+    assert_eq!(
+        GenericParam::bound("starknet::Store", ExprPath::new(["T"])),
+        GenericParam::parse("+starknet::Store<T>").unwrap()
+    );
+    assert_eq!(
+        GenericParam::bound("Into<u8>", parse_type("@T").unwrap()),
+        GenericParam::parse("+Into<u8, @T>").unwrap()
+    );
+    assert_eq!(
+        structure("struct S {}").generic_params_with_bounds(&["Drop"]),
+        None
+    );
+}
+
+#[test]
+fn merges_generic_lists() {
+    let a = structure("struct A<T, +Drop<T>> {}");
+    let b = structure("struct B<T, U, +Drop<T>, +Copy<U>> {}");
+    assert_eq!(
+        a.merged_generic_params(&b).generic_declaration_string(),
+        "<T, +Drop<T>, U, +Copy<U>>"
+    );
+    let c = structure("struct C<const T: u8> {}");
+    assert_eq!(
+        a.merged_generic_params(&c).generic_declaration_string(),
+        "<T, +Drop<T>>"
+    );
+}
+
+#[test]
+#[should_panic(expected = "a bound needs a trait name")]
+fn rejects_bounds_without_a_trait() {
+    GenericParam::bound(ExprPath::new(Vec::<String>::new()), ExprPath::new(["T"]));
+}
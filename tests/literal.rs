@@ -0,0 +1,208 @@
+use cairo_syntax_parser::literal::{
+    decode_short_string, decode_string, encode_short_string, encode_string, short_string_to_felt,
+};
+use cairo_syntax_parser::{
+    ByteArray, CairoFormat, Expr, IntType, Literal, LiteralError, parse_expr,
+};
+use num_bigint::BigInt;
+
+fn literal(text: &str) -> Literal {
+    Literal::parse(text).unwrap_or_else(|error| panic!("{text}: {error}"))
+}
+
+#[test]
+fn parses_radixes_and_suffixes() {
+    assert_eq!(literal("1_u8"), Literal::new(1, Some(IntType::U8)));
+    assert_eq!(literal("1_000_000"), Literal::new(1_000_000, None));
+    assert_eq!(literal("0x1f_u16"), Literal::new(0x1f, Some(IntType::U16)));
+    assert_eq!(
+        literal("0o17_felt252"),
+        Literal::new(0o17, Some(IntType::Felt252))
+    );
+    assert_eq!(literal("0b101_i8"), Literal::new(0b101, Some(IntType::I8)));
+    // Hex digits that look like a suffix are part of the number, as in the compiler.
+    assert_eq!(literal("0x1_f32"), Literal::new(0x1f32, None));
+    assert_eq!(literal("0xff_u8"), Literal::new(0xff, Some(IntType::U8)));
+
+    for text in ["0b", "0o", "0x", "", "_1", "0b2", "u8"] {
+        assert_eq!(
+            Literal::parse(text),
+            Err(LiteralError::Invalid(text.to_string())),
+            "{text}"
+        );
+    }
+    assert_eq!(
+        Literal::parse("1_f32"),
+        Err(LiteralError::UnknownSuffix("f32".to_string()))
+    );
+}
+
+#[test]
+fn checks_ranges_per_type() {
+    for ty in [
+        IntType::U8,
+        IntType::U16,
+        IntType::U32,
+        IntType::U64,
+        IntType::U128,
+        IntType::U256,
+        IntType::Usize,
+        IntType::I8,
+        IntType::I16,
+        IntType::I32,
+        IntType::I64,
+        IntType::I128,
+        IntType::Felt252,
+    ] {
+        let (min, max) = (ty.min(), ty.max());
+        assert_eq!(literal(&format!("{max}_{ty}")).value, max, "{ty}");
+        assert_eq!(literal(&format!("{min}_{ty}")).value, min, "{ty}");
+        let above = &max + 1;
+        assert_eq!(
+            Literal::parse(&format!("{above}_{ty}")),
+            Err(LiteralError::OutOfRange { value: above, ty }),
+            "{ty}"
+        );
+        let below = &min - 1;
+        assert_eq!(
+            Literal::parse(&format!("{below}_{ty}")),
+            Err(LiteralError::OutOfRange { value: below, ty }),
+            "{ty}"
+        );
+    }
+    assert_eq!(IntType::I8.min(), BigInt::from(-128));
+    assert_eq!(IntType::Usize.max(), BigInt::from(u32::MAX));
+    // Unsuffixed literals are not range checked.
+    assert!(Literal::parse(&format!("{}", IntType::U256.max() * 2)).is_ok());
+}
+
+#[test]
+fn reads_and_writes_negative_literals() {
+    assert_eq!(literal("-5_i8"), Literal::new(-5, Some(IntType::I8)));
+    assert_eq!(literal("- 0x10"), Literal::new(-16, None));
+    assert!(literal("-1").is_negative());
+
+    let expr = parse_expr("-1_i8").unwrap();
+    assert_eq!(expr.to_literal(), Ok(Literal::new(-1, Some(IntType::I8))));
+    assert_eq!(
+        parse_expr("-x").unwrap().to_literal(),
+        Err(LiteralError::NotLiteral)
+    );
+
+    let negative = Literal::new(-300, Some(IntType::I16));
+    assert!(matches!(negative.to_expr(), Expr::Unary(_)));
+    assert_eq!(negative.to_expr().stringify(), "-300_i16");
+    assert_eq!(negative.to_expr().to_literal(), Ok(negative));
+    assert_eq!(Expr::from(Literal::new(7, None)).stringify(), "7");
+}
+
+#[test]
+fn decodes_and_encodes_escapes() {
+    assert_eq!(
+        decode_string(r#""a\x41\n\\\'\"""#),
+        Ok("aA\n\\'\"".to_string())
+    );
+    assert_eq!(
+        decode_short_string(r"'\x7f\t\0'_u32"),
+        Ok("\x7f\t\0".to_string())
+    );
+    assert_eq!(
+        decode_string(r#""\q""#),
+        Err(LiteralError::InvalidEscape("\\q".to_string()))
+    );
+    assert_eq!(
+        decode_string(r#""\xg1""#),
+        Err(LiteralError::InvalidEscape("\\xg1".to_string()))
+    );
+    assert_eq!(
+        decode_string(r#""\""#),
+        Err(LiteralError::InvalidEscape("\\".to_string()))
+    );
+
+    let value = "it's\n\\ \"quoted\" \x01";
+    let short = encode_short_string(value).unwrap();
+    assert_eq!(short, r#"'it\'s\n\\ "quoted" \x01'"#);
+    assert_eq!(decode_short_string(&short), Ok(value.to_string()));
+    let string = encode_string(value).unwrap();
+    assert_eq!(string, r#""it's\n\\ \"quoted\" \x01""#);
+    assert_eq!(decode_string(&string), Ok(value.to_string()));
+
+    let expr = Expr::string(value).unwrap();
+    assert_eq!(parse_expr(&expr.stringify()).unwrap(), expr);
+    assert_eq!(expr.to_string_value(), Ok(value.to_string()));
+}
+
+#[test]
+fn limits_short_strings_to_31_bytes() {
+    let longest = "a".repeat(31);
+    assert!(Expr::short_string(&longest).is_ok());
+    assert_eq!(
+        Expr::short_string(&"a".repeat(32)),
+        Err(LiteralError::ShortStringTooLong(32))
+    );
+    assert_eq!(
+        decode_short_string(&format!("'{}'", "a".repeat(32))),
+        Err(LiteralError::ShortStringTooLong(32))
+    );
+    // The limit applies to the decoded bytes, not the escaped text.
+    assert!(decode_short_string(&format!("'{}'", r"\n".repeat(31))).is_ok());
+
+    assert_eq!(
+        short_string_to_felt("hello"),
+        Ok(Literal::from_short_string("'hello'").unwrap().to_felt())
+    );
+    assert_eq!(
+        literal("0x68656c6c6f").value,
+        Literal::from_short_string("'hello'").unwrap().value
+    );
+    assert_eq!(
+        short_string_to_felt(&longest).unwrap(),
+        Literal::from_short_string(&format!("'{longest}'"))
+            .unwrap()
+            .to_felt()
+    );
+    assert_eq!(
+        short_string_to_felt(&"a".repeat(32)),
+        Err(LiteralError::ShortStringTooLong(32))
+    );
+    assert_eq!(
+        Literal::from_short_string("'ab'_u8"),
+        Err(LiteralError::OutOfRange {
+            value: BigInt::from(0x6162),
+            ty: IntType::U8
+        })
+    );
+}
+
+#[test]
+fn rejects_non_ascii() {
+    assert_eq!(Expr::short_string("é"), Err(LiteralError::NonAscii));
+    assert_eq!(Expr::string("日本"), Err(LiteralError::NonAscii));
+    assert_eq!(short_string_to_felt("é"), Err(LiteralError::NonAscii));
+    assert_eq!(decode_string("\"é\""), Err(LiteralError::NonAscii));
+    assert_eq!(decode_short_string(r"'\xff'"), Err(LiteralError::NonAscii));
+}
+
+#[test]
+fn round_trips_byte_arrays_across_words() {
+    for len in [0, 1, 30, 31, 32, 61, 62, 63, 100] {
+        let bytes: Vec<u8> = (0..len).map(|i| b'a' + (i % 26) as u8).collect();
+        let array = ByteArray::from_bytes(&bytes);
+        assert_eq!(array.data.len(), len / 31, "{len}");
+        assert_eq!(array.pending_word_len, len % 31, "{len}");
+        assert_eq!(array.len(), len);
+        assert_eq!(array.is_empty(), len == 0);
+        assert_eq!(array.to_bytes(), bytes, "{len}");
+
+        let text = encode_string(std::str::from_utf8(&bytes).unwrap()).unwrap();
+        assert_eq!(ByteArray::from_literal(&text), Ok(array));
+    }
+
+    let array = ByteArray::from_literal(&format!("\"{}xyz\"", "a".repeat(31))).unwrap();
+    assert_eq!(array.data, vec![[b'a'; 31]]);
+    assert_eq!(array.pending_word, short_string_to_felt("xyz").unwrap());
+    assert_eq!(array.pending_word_len, 3);
+    // Leading zero bytes in the pending word survive the round trip.
+    let zeros = ByteArray::from_bytes(&[0, 0, 1]);
+    assert_eq!(zeros.to_bytes(), vec![0, 0, 1]);
+}
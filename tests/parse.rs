@@ -0,0 +1,105 @@
+use cairo_lang_macro::{TextSpan, Token, TokenStream, TokenTree};
+use cairo_syntax_parser::{
+    CairoFormat, Expr, Parse, ParseError, Span, Spanned, Type, item_from_token_stream,
+    item_from_token_stream_with_diagnostics, items_from_token_stream, str_to_token_stream,
+    try_item_from_token_stream,
+};
+
+#[test]
+fn single_item_entry_points_reject_extra_items() {
+    let two = || str_to_token_stream("struct A {}\nstruct B {}");
+    assert_eq!(item_from_token_stream_with_diagnostics(two()).0, None);
+    assert!(matches!(
+        try_item_from_token_stream(two()),
+        Err(ParseError::Expected("a single item"))
+    ));
+    assert!(matches!(
+        try_item_from_token_stream(str_to_token_stream("")),
+        Err(ParseError::NoItem)
+    ));
+    let (item, diagnostics) =
+        item_from_token_stream_with_diagnostics(str_to_token_stream("struct A {}"));
+    assert!(item.is_some() && diagnostics.is_empty());
+}
+
+#[test]
+fn item_from_token_stream_reports_errors() {
+    let diagnostics = |code: &str| match item_from_token_stream(str_to_token_stream(code)) {
+        Err(ParseError::Diagnostics(diagnostics)) => diagnostics,
+        result => panic!("expected diagnostics, found {result:?}"),
+    };
+    let [empty] = &diagnostics("")[..] else {
+        panic!("expected a single diagnostic");
+    };
+    assert_eq!(empty.message, "expected an item");
+    assert_eq!(empty.span, Span::new(0, 0));
+
+    let [extra] = &diagnostics("struct A {}\nstruct B {}")[..] else {
+        panic!("expected a single diagnostic");
+    };
+    assert_eq!(extra.message, "expected a single item, found 2");
+    assert_eq!(extra.span, Span::new(12, 23));
+
+    let (item, with_diagnostics) =
+        item_from_token_stream_with_diagnostics(str_to_token_stream("struct A {}\nstruct B {}"));
+    assert_eq!((item, with_diagnostics), (None, vec![extra.clone()]));
+
+    assert!(diagnostics("struct A { x: }").iter().all(|d| d.is_error()));
+    assert!(items_from_token_stream(str_to_token_stream("fn f( {}")).is_err());
+    assert_eq!(
+        items_from_token_stream(str_to_token_stream("struct A {}\nstruct B {}"))
+            .unwrap()
+            .len(),
+        2
+    );
+}
+
+#[test]
+fn span_len_does_not_underflow() {
+    assert_eq!(Span::new(2, 5).len(), 3);
+    assert_eq!(Span::new(5, 2).len(), 0);
+}
+
+#[test]
+fn text_span_maps_onto_macro_input() {
+    // `struct S {}` split across tokens placed at offset 100 of the user's file.
+    let token = |content: &str, start: u32| {
+        let end = start + content.len() as u32;
+        TokenTree::Ident(Token::new(content, TextSpan::new(start, end)))
+    };
+    let token_stream = TokenStream::new(vec![
+        token("struct", 100),
+        token(" ", 106),
+        token("S", 107),
+        token(" {}", 110),
+    ]);
+    let item = item_from_token_stream(token_stream.clone()).unwrap();
+    assert_eq!(item.name(), Some("S"));
+    assert_eq!(item.span(), Some(Span::new(0, 11)));
+    let text_span = item.text_span(&token_stream).unwrap();
+    assert_eq!((text_span.start, text_span.end), (100, 113));
+}
+
+#[test]
+fn snippet_spans_are_relative_to_the_snippet() {
+    let Expr::Binary(expr) = Expr::parse("a + b").unwrap() else {
+        panic!("expected a binary expression");
+    };
+    assert_eq!(expr.span(), Some(Span::new(0, 5)));
+    assert_eq!(expr.lhs.span(), Some(Span::new(0, 1)));
+    assert_eq!(expr.rhs.span(), Some(Span::new(4, 5)));
+
+    let (expr, diagnostics) = Expr::parse_with_diagnostics("a +");
+    assert!(expr.is_some());
+    assert!(diagnostics.iter().all(|d| d.span.end <= 3));
+}
+
+#[test]
+fn parses_types() {
+    let ty = Type::parse("Array<(u8, @felt252)>").unwrap();
+    assert_eq!(ty.stringify(), "Array<(u8, @felt252)>");
+    assert!(matches!(
+        Type::parse("1 +"),
+        Err(ParseError::Diagnostics(_))
+    ));
+}
@@ -0,0 +1,56 @@
+use cairo_syntax_parser::expr::GenericArg;
+use cairo_syntax_parser::{CairoFormat, ExprPath, Parse, ParseError, parse_path};
+
+#[test]
+fn parses_paths_with_and_without_generic_separators() {
+    for code in [
+        "core::array::Array::<felt252>",
+        "core::array::Array<felt252>",
+    ] {
+        let path = ExprPath::parse(code).unwrap();
+        assert_eq!(
+            path.idents().collect::<Vec<_>>(),
+            ["core", "array", "Array"]
+        );
+        assert_eq!(path.last_ident(), Some("Array"));
+        assert_eq!(path.generic_args().len(), 1);
+        assert!(matches!(path.generic_args()[0], GenericArg::Unnamed(_)));
+        assert_eq!(path.without_generics().stringify(), "core::array::Array");
+    }
+    assert!(matches!(
+        parse_path("a + b"),
+        Err(ParseError::Diagnostics(_) | ParseError::Expected(_))
+    ));
+}
+
+#[test]
+fn compares_paths_ignoring_generics() {
+    let array = ExprPath::from("core::array::Array<T>");
+    assert!(array.ends_with(&"Array".into()));
+    assert!(array.ends_with(&"array::Array<u8>".into()));
+    assert!(!array.ends_with(&"Span".into()));
+    assert!(array.eq_ignoring_generics(&"core::array::Array".into()));
+    assert!(!array.eq_ignoring_generics(&"Array".into()));
+    assert_ne!(array, ExprPath::from("core::array::Array"));
+}
+
+#[test]
+fn builds_paths_from_segments() {
+    let mut path = ExprPath::new(["starknet", "storage"]);
+    path.push("Map");
+    assert_eq!(path.stringify(), "starknet::storage::Map");
+    assert_eq!(path.prefix().unwrap().stringify(), "starknet::storage");
+    assert_eq!(ExprPath::from("Map").prefix(), None);
+    let joined = path.prefix().unwrap().join(&"Vec<u8>".into());
+    assert_eq!(joined.stringify(), "starknet::storage::Vec<u8>");
+    assert_eq!(joined.first_ident(), Some("starknet"));
+}
+
+#[test]
+fn parses_paths_from_str() {
+    let path: ExprPath = "starknet::storage::Map<u8, u16>".parse().unwrap();
+    assert_eq!(path, ExprPath::from("starknet::storage::Map<u8, u16>"));
+    assert_eq!(path.generic_args().len(), 2);
+    assert!("a + b".parse::<ExprPath>().is_err());
+    assert!("".parse::<ExprPath>().is_err());
+}
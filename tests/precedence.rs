@@ -0,0 +1,100 @@
+use cairo_syntax_parser::expr::{BinaryExpr, BinaryOp, UnaryExpr, UnaryOp};
+use cairo_syntax_parser::{CairoFormat, Expr, ExprPath, Fold, Visitable, parse_expr};
+
+/// Drops the parentheses kept by the parser so a re-parsed tree can be compared with the
+/// synthesized one.
+struct StripParens;
+
+impl Fold for StripParens {
+    fn fold_expr(&mut self, node: Expr) -> Expr {
+        match node.fold_children(self) {
+            Expr::Parenthesized(inner) => *inner,
+            expr => expr,
+        }
+    }
+}
+
+fn var(name: &str) -> Expr {
+    Expr::Path(ExprPath::new([name]))
+}
+
+fn binary(lhs: Expr, op: BinaryOp, rhs: Expr) -> Expr {
+    Expr::Binary(BinaryExpr {
+        lhs: Box::new(lhs),
+        op,
+        rhs: Box::new(rhs),
+        span: None,
+    })
+}
+
+fn unary(op: UnaryOp, expr: Expr) -> Expr {
+    Expr::Unary(UnaryExpr {
+        op,
+        expr: Box::new(expr),
+        span: None,
+    })
+}
+
+#[track_caller]
+fn assert_prints(expr: Expr, expected: &str) {
+    let printed = expr.stringify();
+    assert_eq!(printed, expected);
+    let reparsed = parse_expr(&printed).unwrap().fold(&mut StripParens);
+    assert_eq!(reparsed, expr, "{printed}");
+}
+
+#[test]
+fn parenthesizes_looser_operands() {
+    let sum = binary(var("a"), BinaryOp::Plus, var("b"));
+    assert_prints(binary(sum.clone(), BinaryOp::Mul, var("c")), "(a + b) * c");
+    assert_prints(binary(var("c"), BinaryOp::Mul, sum.clone()), "c * (a + b)");
+    assert_prints(binary(sum, BinaryOp::Plus, var("c")), "a + b + c");
+    let product = binary(var("a"), BinaryOp::Mul, var("b"));
+    assert_prints(binary(product, BinaryOp::Plus, var("c")), "a * b + c");
+}
+
+#[test]
+fn respects_associativity() {
+    let inner = binary(var("b"), BinaryOp::Minus, var("c"));
+    assert_prints(binary(var("a"), BinaryOp::Minus, inner), "a - (b - c)");
+    let inner = binary(var("a"), BinaryOp::Minus, var("b"));
+    assert_prints(binary(inner, BinaryOp::Minus, var("c")), "a - b - c");
+    // Comparisons do not chain, so both sides need parentheses.
+    let inner = binary(var("a"), BinaryOp::LT, var("b"));
+    assert_prints(binary(inner, BinaryOp::EqEq, var("c")), "(a < b) == c");
+}
+
+#[test]
+fn parenthesizes_binary_operands_of_unary_operators() {
+    let sum = binary(var("a"), BinaryOp::Plus, var("b"));
+    assert_prints(unary(UnaryOp::Minus, sum.clone()), "-(a + b)");
+    assert_prints(unary(UnaryOp::Not, sum), "!(a + b)");
+    let field = binary(var("a"), BinaryOp::Dot, var("b"));
+    assert_prints(unary(UnaryOp::Minus, field), "-a.b");
+    assert_prints(
+        binary(unary(UnaryOp::Minus, var("a")), BinaryOp::Mul, var("b")),
+        "-a * b",
+    );
+    assert_prints(
+        binary(var("a"), BinaryOp::Minus, unary(UnaryOp::Minus, var("b"))),
+        "a - -b",
+    );
+}
+
+#[test]
+fn parenthesizes_unary_operands_of_postfix_operators() {
+    let negated = unary(UnaryOp::Minus, var("a"));
+    assert_prints(Expr::ErrorPropagate(Box::new(negated.clone())), "(-a)?");
+    assert_prints(binary(negated, BinaryOp::Dot, var("b")), "(-a).b");
+    let desnapped = unary(UnaryOp::Desnap, var("a"));
+    assert_prints(binary(desnapped, BinaryOp::Dot, var("b")), "(*a).b");
+    assert_prints(Expr::ErrorPropagate(Box::new(var("a"))), "a?");
+}
+
+#[test]
+fn separates_nested_references() {
+    let reference = unary(UnaryOp::Reference, var("x"));
+    assert_prints(unary(UnaryOp::Reference, reference), "& &x");
+    let snapshot = unary(UnaryOp::At, var("x"));
+    assert_prints(unary(UnaryOp::Reference, snapshot), "&@x");
+}
@@ -0,0 +1,85 @@
+use cairo_syntax_parser::{
+    CairoFormat, Expr, Item, ParseError, cairo_quote, parse_expr, quote::expand,
+};
+
+fn exprs(sources: &[&str]) -> Vec<Expr> {
+    sources.iter().map(|s| parse_expr(s).unwrap()).collect()
+}
+
+fn quote_error<T: std::fmt::Debug>(result: Result<T, ParseError>) -> String {
+    match result {
+        Err(ParseError::Quote(message)) => message,
+        other => panic!("expected a quote error, got {other:?}"),
+    }
+}
+
+#[test]
+fn interpolates_single_values() {
+    let lhs = parse_expr("a * b").unwrap();
+    let expr: Expr = cairo_quote!("#lhs + #rhs", lhs, rhs = "1").unwrap();
+    assert_eq!(expr.stringify(), "a * b + 1");
+    // `#` inside string literals is left alone.
+    let expr: Expr = cairo_quote!("f(\"#lhs\", #lhs)", lhs).unwrap();
+    assert_eq!(expr.stringify(), "f(\"#lhs\", a * b)");
+}
+
+#[test]
+fn expands_repetitions() {
+    let args = exprs(&["x", "y + 1"]);
+    let names = vec!["first", "second"];
+    let item: Item = cairo_quote!(
+        "fn #name() { g(#(#args),*); #(let #names = #args;)* }",
+        name = "f",
+        args,
+        names,
+    )
+    .unwrap();
+    assert_eq!(
+        item.stringify(),
+        "fn f() {\ng(x, y + 1);\nlet first = x;\nlet second = y + 1;\n}"
+    );
+
+    let empty: Vec<Expr> = vec![];
+    let expr: Expr = cairo_quote!("g(#(#empty),*)", empty).unwrap();
+    assert_eq!(expr.stringify(), "g()");
+}
+
+#[test]
+fn rejects_repeated_values_outside_repetitions() {
+    let args = exprs(&["x", "y"]);
+    let message = quote_error(cairo_quote!("g(#args)", args) as Result<Expr, _>);
+    assert!(
+        message.contains("`#args` holds several values"),
+        "{message}"
+    );
+}
+
+#[test]
+fn rejects_invalid_templates() {
+    let args = exprs(&["x", "y"]);
+    let single = parse_expr("z").unwrap();
+    let unequal = exprs(&["u"]);
+    assert_eq!(
+        quote_error(expand("#(#args", &[])),
+        "unclosed `#(` repetition"
+    );
+    assert_eq!(
+        quote_error(cairo_quote!("g(#(#args),)", args) as Result<Expr, _>),
+        "expected `*` after `#(...)` repetition"
+    );
+    assert_eq!(
+        quote_error(cairo_quote!("g(#(#single),*)", single) as Result<Expr, _>),
+        "repetition contains no repeated variable"
+    );
+    assert_eq!(
+        quote_error(cairo_quote!("g(#(#args + #unequal),*)", args, unequal) as Result<Expr, _>),
+        "`#unequal` has 1 values, expected 2"
+    );
+    assert_eq!(
+        quote_error(cairo_quote!("g(#missing)") as Result<Expr, _>),
+        "unbound variable `#missing`"
+    );
+    // Templates that expand but do not parse report the parser's diagnostics.
+    let result: Result<Expr, _> = cairo_quote!("g(#single,, )", single);
+    assert!(matches!(result, Err(ParseError::Diagnostics(_))));
+}
@@ -0,0 +1,178 @@
+//! Parses every Cairo file in `tests/fixtures`, prints it back and checks that re-parsing the
+//! output gives the same AST.
+
+use std::fs;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::path::PathBuf;
+
+use cairo_syntax_parser::{
+    CairoFormat, FormatConfig, Item, Parse, ParseError, Span, VisitMut, Visitable,
+};
+
+struct ClearSpans;
+
+impl VisitMut for ClearSpans {
+    fn visit_span_mut(&mut self, span: &mut Option<Span>) {
+        *span = None;
+    }
+}
+
+fn fixtures() -> Vec<(String, String)> {
+    let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+    let mut paths: Vec<PathBuf> = fs::read_dir(&dir)
+        .unwrap_or_else(|e| panic!("reading {}: {e}", dir.display()))
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "cairo"))
+        .collect();
+    paths.sort();
+    assert!(!paths.is_empty(), "no fixtures in {}", dir.display());
+    paths
+        .into_iter()
+        .map(|path| {
+            let name = path.file_name().unwrap().to_string_lossy().into_owned();
+            (name, fs::read_to_string(&path).unwrap())
+        })
+        .collect()
+}
+
+fn parse(name: &str, stage: &str, code: &str) -> Vec<Item> {
+    Vec::<Item>::parse(code).unwrap_or_else(|error| {
+        let context = match &error {
+            ParseError::Diagnostics(diagnostics) => diagnostics
+                .iter()
+                .map(|d| format!("{d}\n  at: {}", line_at(code, d.span.start as usize)))
+                .collect::<Vec<_>>()
+                .join("\n"),
+            error => error.to_string(),
+        };
+        panic!("{name}: failed to parse {stage}:\n{context}")
+    })
+}
+
+fn line_at(code: &str, offset: usize) -> &str {
+    let start = code[..offset].rfind('\n').map_or(0, |i| i + 1);
+    let end = code[offset..].find('\n').map_or(code.len(), |i| offset + i);
+    code[start..end].trim()
+}
+
+/// Describes the first node where the trees differ, as the chain of enclosing nodes in their
+/// debug output followed by the differing lines.
+fn first_divergence(expected: &[Item], actual: &[Item]) -> Option<String> {
+    if expected.len() != actual.len() {
+        return Some(format!(
+            "expected {} items, found {}",
+            expected.len(),
+            actual.len()
+        ));
+    }
+    let (index, (expected, actual)) = expected
+        .iter()
+        .zip(actual)
+        .enumerate()
+        .find(|(_, (expected, actual))| expected != actual)?;
+    let debug = |item: &Item| {
+        let mut item = item.clone();
+        item.accept_mut(&mut ClearSpans);
+        format!("{item:#?}")
+    };
+    let (expected, actual) = (debug(expected), debug(actual));
+    let (expected, actual): (Vec<&str>, Vec<&str>) =
+        (expected.lines().collect(), actual.lines().collect());
+    let line = expected
+        .iter()
+        .zip(&actual)
+        .position(|(e, a)| e != a)
+        .unwrap_or(expected.len().min(actual.len()));
+    let indent = |line: &str| line.len() - line.trim_start().len();
+    let mut path = Vec::new();
+    let mut depth = expected.get(line).map_or(usize::MAX, |l| indent(l));
+    for previous in expected[..line].iter().rev() {
+        if indent(previous) < depth {
+            depth = indent(previous);
+            if let Some(node) = previous.trim().strip_suffix(" {") {
+                path.push(node);
+            }
+        }
+    }
+    path.reverse();
+    Some(format!(
+        "item {index} differs at {}\n  expected: {}\n     found: {}",
+        path.join(" > "),
+        expected.get(line).map_or("<end>", |l| l.trim()),
+        actual.get(line).map_or("<end>", |l| l.trim()),
+    ))
+}
+
+fn assert_round_trips(print: impl Fn(&Vec<Item>) -> String) {
+    for (name, code) in fixtures() {
+        let items = parse(&name, "fixture", &code);
+        let printed = print(&items);
+        let reparsed = parse(&name, "printed output", &printed);
+        if let Some(divergence) = first_divergence(&items, &reparsed) {
+            panic!("{name}: {divergence}\n--- printed ---\n{printed}");
+        }
+        assert_eq!(
+            hash(&items),
+            hash(&reparsed),
+            "{name}: equal items hash differently"
+        );
+    }
+}
+
+fn hash(items: &[Item]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    items.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[test]
+fn stringify_round_trips() {
+    assert_round_trips(|items| items.stringify());
+}
+
+#[test]
+fn pretty_round_trips() {
+    assert_round_trips(|items| items.pretty());
+}
+
+#[test]
+fn pretty_compact_round_trips() {
+    assert_round_trips(|items| items.pretty_with(&FormatConfig::compact()));
+}
+
+#[test]
+fn pretty_is_idempotent() {
+    for (name, code) in fixtures() {
+        let printed = parse(&name, "fixture", &code).pretty();
+        let reprinted = parse(&name, "printed output", &printed).pretty();
+        assert_eq!(
+            printed, reprinted,
+            "{name}: pretty output changes when re-printed"
+        );
+    }
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_round_trips() {
+    for (name, code) in fixtures() {
+        let items = parse(&name, "fixture", &code);
+        let json = serde_json::to_string(&items).unwrap();
+        let decoded: Vec<Item> = serde_json::from_str(&json)
+            .unwrap_or_else(|e| panic!("{name}: failed to deserialize: {e}"));
+        if let Some(divergence) = first_divergence(&items, &decoded) {
+            panic!("{name}: {divergence}");
+        }
+        assert_eq!(
+            items
+                .iter()
+                .map(cairo_syntax_parser::Spanned::span)
+                .collect::<Vec<_>>(),
+            decoded
+                .iter()
+                .map(cairo_syntax_parser::Spanned::span)
+                .collect::<Vec<_>>(),
+            "{name}: spans changed",
+        );
+    }
+}
@@ -0,0 +1,86 @@
+use cairo_syntax_parser::expr::GenericArg;
+use cairo_syntax_parser::{
+    CairoFormat, Expr, Item, Parse, Span, Spanned, Type, items_from_token_stream,
+    str_to_token_stream,
+};
+
+fn parse(code: &str) -> Type {
+    Type::parse(code).unwrap_or_else(|error| panic!("{code}: {error}"))
+}
+
+#[test]
+fn reads_and_prints_types() {
+    for (code, check) in [
+        (
+            "core::array::Array<felt252>",
+            (|ty| matches!(ty, Type::Path(_))) as fn(&Type) -> bool,
+        ),
+        (
+            "(u8, felt252)",
+            |ty| matches!(ty, Type::Tuple(tuple) if tuple.types.len() == 2),
+        ),
+        (
+            "(u8,)",
+            |ty| matches!(ty, Type::Tuple(tuple) if tuple.types.len() == 1),
+        ),
+        ("()", |ty| matches!(ty, Type::Unit)),
+        (
+            "@Array<u8>",
+            |ty| matches!(ty, Type::Snapshot(inner) if matches!(**inner, Type::Path(_))),
+        ),
+        ("&u8", |ty| matches!(ty, Type::Reference(_))),
+        ("[u8; 4]", |ty| matches!(ty, Type::FixedSizeArray(_))),
+        ("_", |ty| matches!(ty, Type::Infer)),
+        ("never", |ty| matches!(ty, Type::Never)),
+    ] {
+        let ty = parse(code);
+        assert!(check(&ty), "{code}: {ty:?}");
+        assert_eq!(ty.stringify(), code.replace("(u8,)", "(u8, )"));
+        assert_eq!(parse(&ty.stringify()), ty, "{code}");
+    }
+}
+
+#[test]
+fn keeps_other_expressions_in_type_position() {
+    let items = items_from_token_stream(str_to_token_stream(
+        "fn f() {\n    let x = g::<{ 1 + 2 }>();\n}\n",
+    ))
+    .unwrap();
+    let printed = items.stringify();
+    assert!(printed.contains("g::<{\n1 + 2\n}>()"), "{printed}");
+    assert_eq!(
+        items_from_token_stream(str_to_token_stream(&printed)).unwrap(),
+        items
+    );
+
+    let ty = parse("[u8, u16; 2]");
+    assert!(matches!(&ty, Type::Expr(expr) if matches!(**expr, Expr::FixedSizeArray(_))));
+    assert_eq!(ty.stringify(), "[u8, u16; 2]");
+    let Item::TypeAlias(alias) = Item::parse("type T = [u8, u16; 2];").unwrap() else {
+        panic!("expected a type alias");
+    };
+    assert_eq!(alias.ty, ty);
+}
+
+#[test]
+fn reads_generic_args_as_types() {
+    let Type::Path(path) = parse("Array<@(u8, [felt252; 2])>") else {
+        panic!("expected a path");
+    };
+    let [GenericArg::Unnamed(Type::Snapshot(inner))] = path.generic_args() else {
+        panic!("expected a snapshot argument: {path:?}");
+    };
+    let Type::Tuple(tuple) = inner.as_ref() else {
+        panic!("expected a tuple: {inner:?}");
+    };
+    assert_eq!(tuple.span, Some(Span::new(7, 25)));
+    assert!(matches!(
+        &tuple.types[1],
+        Type::FixedSizeArray(array) if array.span == Some(Span::new(12, 24))
+    ));
+    assert_eq!(inner.span(), tuple.span);
+    assert_eq!(
+        tuple.types[1],
+        Type::fixed_size_array(parse("felt252"), Expr::Literal("2".to_string()))
+    );
+}
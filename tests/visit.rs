@@ -0,0 +1,122 @@
+use cairo_syntax_parser::common::Param;
+use cairo_syntax_parser::expr::FunctionCall;
+use cairo_syntax_parser::{
+    CairoFormat, Expr, ExprPath, Fold, Item, PathSegment, Span, Statement, Visit, VisitMut,
+    Visitable, item_from_token_stream, parse_expr, str_to_token_stream,
+};
+
+fn function() -> Item {
+    item_from_token_stream(str_to_token_stream(
+        "fn f(a: u8) -> u8 {\n    let b = g(a, 1);\n    h::<u8>(b)\n}",
+    ))
+    .unwrap()
+}
+
+#[derive(Default)]
+struct Counter {
+    calls: usize,
+    statements: usize,
+    spans: usize,
+}
+
+impl Visit for Counter {
+    fn visit_span(&mut self, _span: &Option<Span>) {
+        self.spans += 1;
+    }
+
+    fn visit_function_call(&mut self, node: &FunctionCall) {
+        self.calls += 1;
+        node.walk(self);
+    }
+
+    fn visit_statement(&mut self, node: &Statement) {
+        self.statements += 1;
+        node.walk(self);
+    }
+}
+
+#[test]
+fn counts_nodes() {
+    let mut counter = Counter::default();
+    function().accept(&mut counter);
+    assert_eq!(counter.calls, 2);
+    assert_eq!(counter.statements, 2);
+    assert!(counter.spans > counter.calls + counter.statements);
+}
+
+#[derive(Default)]
+struct Paths(Vec<String>);
+
+impl Visit for Paths {
+    fn visit_expr_path(&mut self, node: &ExprPath) {
+        self.0.push(node.stringify());
+        node.walk(self);
+    }
+}
+
+#[test]
+fn collects_paths_in_source_order() {
+    let mut paths = Paths::default();
+    function().accept(&mut paths);
+    // Let bindings and generic arguments are paths too.
+    assert_eq!(paths.0, ["u8", "u8", "b", "g", "a", "h::<u8>", "u8", "b"]);
+}
+
+struct RenameVariable {
+    from: &'static str,
+    to: &'static str,
+}
+
+impl VisitMut for RenameVariable {
+    fn visit_param_mut(&mut self, node: &mut Param) {
+        if node.name == self.from {
+            node.name = self.to.to_string();
+        }
+        node.walk_mut(self);
+    }
+
+    fn visit_path_segment_mut(&mut self, node: &mut PathSegment) {
+        if let PathSegment::Simple(name) = node
+            && name == self.from
+        {
+            *name = self.to.to_string();
+        }
+    }
+}
+
+#[test]
+fn visit_mut_renames_identifiers() {
+    let mut item = function();
+    item.accept_mut(&mut RenameVariable {
+        from: "a",
+        to: "amount",
+    });
+    assert_eq!(
+        item.pretty(),
+        "fn f(amount: u8) -> u8 {\n    let b = g(amount, 1);\n    h::<u8>(b)\n}"
+    );
+}
+
+/// Replaces every call to `g` with its first argument doubled.
+struct InlineG;
+
+impl Fold for InlineG {
+    fn fold_expr(&mut self, node: Expr) -> Expr {
+        match node.fold_children(self) {
+            Expr::FunctionCall(call) if call.path.stringify() == "g" => {
+                let arg = call.args[0].stringify();
+                parse_expr(&format!("{arg} * 2")).unwrap()
+            }
+            expr => expr,
+        }
+    }
+}
+
+#[test]
+fn fold_replaces_expressions() {
+    let item = function().fold(&mut InlineG);
+    assert_eq!(
+        item.pretty(),
+        "fn f(a: u8) -> u8 {\n    let b = a * 2;\n    h::<u8>(b)\n}"
+    );
+}